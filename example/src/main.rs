@@ -1,6 +1,6 @@
 use leptos::{ev, prelude::*};
 use leptos_drag_reorder::{
-    provide_drag_reorder, use_drag_reorder, HoverPosition, UseDragReorderReturn,
+    provide_drag_reorder, use_drag_handle, use_drag_reorder, HoverPosition, UseDragReorderReturn,
 };
 
 fn main() {
@@ -31,11 +31,11 @@ fn App() -> impl IntoView {
     ]);
     let panel_order = [
         // Column 1
-        RwSignal::new(vec!["1".into(), "3".into()]),
+        RwSignal::new(vec![1, 3]),
         // Column 2
-        RwSignal::new(vec!["2".into()]),
+        RwSignal::new(vec![2]),
     ];
-    let column_refs = provide_drag_reorder(panel_order);
+    let (column_refs, handle) = provide_drag_reorder(panel_order);
 
     let columns = panel_order
         .into_iter()
@@ -49,7 +49,7 @@ fn App() -> impl IntoView {
                         panels
                             .read()
                             .iter()
-                            .find(|panel| &panel.id.to_string() == id)
+                            .find(|panel| panel.id == *id)
                             .cloned()
                     })
                     .collect::<Vec<_>>()
@@ -78,7 +78,7 @@ fn App() -> impl IntoView {
                 title: format!("Panel #{next_id}"),
             });
             panel_order[0].update(|order| {
-                order.insert(0, next_id.to_string().into());
+                order.insert(0, next_id);
             });
         }
     };
@@ -86,6 +86,12 @@ fn App() -> impl IntoView {
     view! {
         <div class="root">
             <button on:click=add_panel>"Add Panel"</button>
+            <button on:click=move |_| handle.undo() disabled=move || !handle.can_undo().get()>
+                "Undo"
+            </button>
+            <button on:click=move |_| handle.redo() disabled=move || !handle.can_redo().get()>
+                "Redo"
+            </button>
 
             <div class="row">
                 {columns}
@@ -99,12 +105,15 @@ fn Panel(id: u32, title: String) -> impl IntoView {
     let UseDragReorderReturn {
         node_ref,
         draggable,
-        set_draggable,
         hover_position,
         on_dragstart,
         on_dragend,
+        on_keydown,
+        is_selected,
+        on_click,
         ..
-    } = use_drag_reorder(id.to_string());
+    } = use_drag_reorder(id);
+    let handle_ref = use_drag_handle();
 
     view! {
         <div
@@ -112,11 +121,15 @@ fn Panel(id: u32, title: String) -> impl IntoView {
             class="panel"
             class=("panel--above", move || matches!(hover_position.get(), Some(HoverPosition::Above)))
             class=("panel--below", move || matches!(hover_position.get(), Some(HoverPosition::Below)))
+            class=("panel--selected", is_selected)
             draggable=move || draggable.get().then_some("true")
             on:dragstart=on_dragstart
             on:dragend=on_dragend
-            on:mousedown=move |_| set_draggable(true)
+            on:keydown=on_keydown
+            on:click=on_click
+            tabindex="0"
         >
+            <span node_ref=handle_ref class="panel__handle">"⠿"</span>
             {title}
         </div>
     }
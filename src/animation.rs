@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use js_sys::{Array, Function, Object, Reflect};
+use leptos::prelude::*;
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::PanelId;
+
+/// Animation of panels moving to their new position after a reorder, passed to
+/// [`DragReorderOptions::animation`](crate::DragReorderOptions::animation).
+///
+/// Panels are animated with the Web Animations API, from their previous position to their new one.
+/// Animations are skipped when the user prefers reduced motion.
+#[derive(Clone, Debug)]
+pub struct ReorderAnimation {
+    duration: f64,
+    easing: Oco<'static, str>,
+}
+
+impl Default for ReorderAnimation {
+    fn default() -> Self {
+        ReorderAnimation {
+            duration: 150.0,
+            easing: Oco::Borrowed("ease-out"),
+        }
+    }
+}
+
+impl ReorderAnimation {
+    /// Sets the duration of the animation in milliseconds. Defaults to 150.
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the CSS easing function of the animation. Defaults to `ease-out`.
+    pub fn easing(mut self, easing: impl Into<Oco<'static, str>>) -> Self {
+        self.easing = easing.into();
+        self
+    }
+}
+
+/// The elements of each panel, keyed by panel ID.
+pub(crate) type PanelElements<K> = RwSignal<HashMap<K, SendWrapper<web_sys::Element>>>;
+
+/// Runs a reorder, animating panels of the given providers from their previous position to their new one.
+///
+/// Panels are matched by ID, so panels moved to another provider are animated too.
+pub(crate) fn animate_reorder<K: PanelId>(
+    animation: Option<&ReorderAnimation>,
+    panels: &[PanelElements<K>],
+    reorder: impl FnOnce(),
+) {
+    // Panels aren't rendered on the server, so there's nothing to animate
+    let Some(animation) = animation.filter(|_| !cfg!(feature = "ssr") && !prefers_reduced_motion())
+    else {
+        reorder();
+        return;
+    };
+
+    // First
+    let first = panels
+        .iter()
+        .flat_map(|panels| {
+            panels
+                .read_untracked()
+                .iter()
+                .map(|(id, el)| {
+                    let rect = el.get_bounding_client_rect();
+                    (id.clone(), (rect.x(), rect.y()))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<HashMap<_, _>>();
+
+    reorder();
+
+    // Last, invert and play once the panels have been rendered in their new position
+    let panels = panels.to_vec();
+    let animation = animation.clone();
+    request_animation_frame(move || {
+        for panels in panels {
+            for (id, el) in panels.read_untracked().iter() {
+                let Some((first_x, first_y)) = first.get(id) else {
+                    continue;
+                };
+                let rect = el.get_bounding_client_rect();
+                let delta_x = first_x - rect.x();
+                let delta_y = first_y - rect.y();
+                if delta_x != 0.0 || delta_y != 0.0 {
+                    play(el, &animation, delta_x, delta_y);
+                }
+            }
+        }
+    });
+}
+
+/// Plays an animation from the panel's previous position with `Element.animate`, which is called dynamically
+/// since it's unstable in `web-sys`.
+fn play(el: &web_sys::Element, animation: &ReorderAnimation, delta_x: f64, delta_y: f64) {
+    let object = |properties: &[(&str, JsValue)]| {
+        let object = Object::new();
+        for (key, value) in properties {
+            let _ = Reflect::set(&object, &(*key).into(), value);
+        }
+        object
+    };
+
+    let keyframes = Array::of2(
+        &object(&[(
+            "transform",
+            format!("translate({delta_x}px, {delta_y}px)").into(),
+        )]),
+        &object(&[("transform", "none".into())]),
+    );
+    let options = object(&[
+        ("duration", animation.duration.into()),
+        ("easing", animation.easing.as_str().into()),
+    ]);
+
+    if let Ok(animate) =
+        Reflect::get(el, &"animate".into()).and_then(|animate| animate.dyn_into::<Function>())
+    {
+        let _ = animate.call2(el, &keyframes, &options);
+    }
+}
+
+fn prefers_reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+
+use leptos::prelude::*;
+use send_wrapper::SendWrapper;
+
+/// A drag operation announced to screen readers.
+///
+/// Column and panel indexes are zero based.
+///
+/// The default message doesn't mention the panel, since IDs are rarely meaningful to users.
+/// Use [`DragReorderOptions::announcement_formatter`](crate::DragReorderOptions::announcement_formatter)
+/// to include a panel name looked up from the ID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Announcement<K> {
+    /// What happened to the panel.
+    pub kind: AnnouncementKind,
+    /// The ID of the dragged panel.
+    pub id: K,
+    /// The column the panel is in, or is hovering over.
+    pub column_index: usize,
+    /// The position of the panel within the column.
+    pub index: usize,
+    /// The number of panels in the column, including the dragged panel.
+    pub column_len: usize,
+}
+
+/// The kind of drag operation being announced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnnouncementKind {
+    /// The panel was picked up from its current position.
+    PickedUp,
+    /// The dragged panel moved over a new position.
+    Moved,
+    /// The panel was dropped into its new position.
+    Dropped,
+    /// The drag was cancelled, and the panel returned to its original position.
+    Cancelled,
+}
+
+impl<K> fmt::Display for Announcement<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Announcement {
+            kind,
+            id: _,
+            column_index,
+            index,
+            column_len,
+        } = self;
+        let column = column_index + 1;
+        let position = index + 1;
+        match kind {
+            AnnouncementKind::PickedUp => write!(
+                f,
+                "Picked up panel, position {position} of {column_len} in column {column}"
+            ),
+            AnnouncementKind::Moved => write!(
+                f,
+                "Moved to column {column}, position {position} of {column_len}"
+            ),
+            AnnouncementKind::Dropped => write!(
+                f,
+                "Dropped panel in column {column}, position {position} of {column_len}"
+            ),
+            AnnouncementKind::Cancelled => write!(
+                f,
+                "Cancelled dragging panel, returned to column {column}, position {position} of {column_len}"
+            ),
+        }
+    }
+}
+
+/// Renders announcements into a visually hidden `aria-live` region.
+#[derive(Clone)]
+pub(crate) struct Announcer<K: 'static> {
+    region: RwSignal<Option<SendWrapper<web_sys::Element>>>,
+    formatter: Option<Callback<Announcement<K>, String>>,
+}
+
+impl<K: 'static> Announcer<K> {
+    /// Appends the live region to the document body once running on the client, removing it when the
+    /// owner is cleaned up.
+    pub(crate) fn new(formatter: Option<Callback<Announcement<K>, String>>) -> Self {
+        let region = RwSignal::new(None);
+
+        Effect::new(move |_| {
+            let document = document();
+            let el = document.create_element("div").ok().and_then(|el| {
+                let _ = el.set_attribute("aria-live", "assertive");
+                let _ = el.set_attribute("aria-atomic", "true");
+                let _ = el.set_attribute(
+                    "style",
+                    "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; \
+                     overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;",
+                );
+                document.body()?.append_child(&el).ok()?;
+                Some(SendWrapper::new(el))
+            });
+            region.set(el);
+        });
+
+        on_cleanup(move || {
+            if let Some(region) = region.try_update_untracked(Option::take).flatten() {
+                region.remove();
+            }
+        });
+
+        Announcer { region, formatter }
+    }
+
+    /// Announces a drag operation, unless the formatter returns an empty message.
+    pub(crate) fn announce(&self, announcement: Announcement<K>) {
+        let region = self.region.read_untracked();
+        let Some(region) = region.as_ref() else {
+            return;
+        };
+
+        let message = match self.formatter {
+            Some(formatter) => formatter.run(announcement),
+            None => announcement.to_string(),
+        };
+        if !message.is_empty() {
+            region.set_text_content(Some(&message));
+        }
+    }
+}
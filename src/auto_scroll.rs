@@ -0,0 +1,182 @@
+use leptos::prelude::*;
+
+use crate::{ColumnRefs, PanelId};
+
+/// Scrolling of the window and the scroll containers of columns while a panel is dragged near their edges,
+/// passed to [`DragReorderOptions::auto_scroll`](crate::DragReorderOptions::auto_scroll).
+///
+/// The closer the pointer is to an edge, the faster it scrolls.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoScroll {
+    threshold: f64,
+    max_speed: f64,
+}
+
+impl Default for AutoScroll {
+    fn default() -> Self {
+        AutoScroll {
+            threshold: 48.0,
+            max_speed: 20.0,
+        }
+    }
+}
+
+impl AutoScroll {
+    /// Sets the distance in pixels from an edge at which scrolling starts. Defaults to 48.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the speed in pixels per frame when the pointer reaches an edge. Defaults to 20.
+    ///
+    /// Setting the speed to `0` disables auto scrolling.
+    pub fn max_speed(mut self, max_speed: f64) -> Self {
+        self.max_speed = max_speed;
+        self
+    }
+
+    /// Returns the scroll speed along an axis, for a pointer at `position` within `start..end`.
+    fn speed(&self, position: f64, start: f64, end: f64) -> f64 {
+        let threshold = self.threshold.min((end - start) / 2.0);
+        if threshold <= 0.0 || position < start || position > end {
+            return 0.0;
+        }
+        if position < start + threshold {
+            -self.max_speed * (1.0 - (position - start) / threshold)
+        } else if position > end - threshold {
+            self.max_speed * (1.0 - (end - position) / threshold)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Scrolls every animation frame while the pointer is near an edge.
+#[derive(Clone, Copy)]
+pub(crate) struct AutoScroller {
+    options: AutoScroll,
+    pointer: RwSignal<Option<(f64, f64)>>,
+    running: RwSignal<bool>,
+}
+
+impl AutoScroller {
+    pub(crate) fn new(options: AutoScroll) -> Self {
+        AutoScroller {
+            options,
+            pointer: RwSignal::new(None),
+            running: RwSignal::new(false),
+        }
+    }
+
+    /// Updates the pointer position, starting to scroll if it's near an edge.
+    pub(crate) fn update<K: PanelId>(
+        &self,
+        column_refs: ColumnRefs<K>,
+        mouse_x: f64,
+        mouse_y: f64,
+    ) {
+        if self.options.max_speed <= 0.0 {
+            return;
+        }
+        self.pointer.set(Some((mouse_x, mouse_y)));
+        if !self.running.get_untracked() {
+            self.running.set(true);
+            self.tick(column_refs);
+        }
+    }
+
+    /// Stops scrolling, once the drag has ended.
+    pub(crate) fn stop(&self) {
+        self.pointer.set(None);
+    }
+
+    fn tick<K: PanelId>(self, column_refs: ColumnRefs<K>) {
+        let Some((mouse_x, mouse_y)) = self.pointer.get_untracked() else {
+            self.running.set(false);
+            return;
+        };
+
+        let mut scrolled = false;
+        for container in scroll_containers(column_refs) {
+            let rect = container.get_bounding_client_rect();
+            let delta_x = self.options.speed(mouse_x, rect.left(), rect.right());
+            let delta_y = self.options.speed(mouse_y, rect.top(), rect.bottom());
+            scrolled |= scroll_by(delta_x, delta_y, |x, y| {
+                let (left, top) = (container.scroll_left(), container.scroll_top());
+                container.scroll_by_with_x_and_y(x, y);
+                left != container.scroll_left() || top != container.scroll_top()
+            });
+        }
+
+        let window = window();
+        let width = window.inner_width().ok().and_then(|width| width.as_f64());
+        let height = window
+            .inner_height()
+            .ok()
+            .and_then(|height| height.as_f64());
+        if let (Some(width), Some(height)) = (width, height) {
+            let delta_x = self.options.speed(mouse_x, 0.0, width);
+            let delta_y = self.options.speed(mouse_y, 0.0, height);
+            scrolled |= scroll_by(delta_x, delta_y, |x, y| {
+                let (left, top) = (window.scroll_x(), window.scroll_y());
+                window.scroll_by_with_x_and_y(x, y);
+                left.ok() != window.scroll_x().ok() || top.ok() != window.scroll_y().ok()
+            });
+        }
+
+        // Keep scrolling while the pointer stays near an edge, since no events fire while it's still
+        if scrolled {
+            request_animation_frame(move || self.tick(column_refs));
+        } else {
+            self.running.set(false);
+        }
+    }
+}
+
+/// Scrolls by the given amount unless it's zero, returning `true` if the scroll position changed.
+fn scroll_by(delta_x: f64, delta_y: f64, scroll: impl FnOnce(f64, f64) -> bool) -> bool {
+    if delta_x == 0.0 && delta_y == 0.0 {
+        return false;
+    }
+    // Browsers ignore scrolling by less than a pixel
+    let at_least_one_pixel = |delta: f64| {
+        if delta == 0.0 {
+            0.0
+        } else {
+            delta.signum() * delta.abs().max(1.0)
+        }
+    };
+    scroll(at_least_one_pixel(delta_x), at_least_one_pixel(delta_y))
+}
+
+/// Returns the columns and their ancestors which can be scrolled, excluding the document.
+fn scroll_containers<K: PanelId>(column_refs: ColumnRefs<K>) -> Vec<web_sys::Element> {
+    let mut containers: Vec<web_sys::Element> = Vec::new();
+    for column in column_refs.read_untracked().values() {
+        let mut el = Some(web_sys::Element::clone(column));
+        while let Some(current) = el {
+            if matches!(current.tag_name().as_str(), "BODY" | "HTML") {
+                break;
+            }
+            if is_scrollable(&current) && !containers.contains(&current) {
+                containers.push(current.clone());
+            }
+            el = current.parent_element();
+        }
+    }
+    containers
+}
+
+fn is_scrollable(el: &web_sys::Element) -> bool {
+    let Ok(Some(style)) = window().get_computed_style(el) else {
+        return false;
+    };
+    let overflows = |property: &str| {
+        style
+            .get_property_value(property)
+            .is_ok_and(|overflow| matches!(overflow.as_str(), "auto" | "scroll" | "overlay"))
+    };
+    (overflows("overflow-y") && el.scroll_height() > el.client_height())
+        || (overflows("overflow-x") && el.scroll_width() > el.client_width())
+}
@@ -0,0 +1,91 @@
+use std::{fmt, sync::Arc};
+
+use leptos::{
+    prelude::*,
+    tachys::view::{Mountable, Render},
+};
+
+/// Custom drag image set with [`UseDragReorderOptions`](crate::UseDragReorderOptions).
+#[derive(Clone)]
+pub(crate) enum DragImage {
+    /// An existing element, typically from a node ref.
+    Element(Arc<dyn Fn() -> Option<web_sys::Element> + Send + Sync>),
+    /// A view rendered for the duration of the drag start.
+    Render(Arc<dyn Fn() -> AnyView + Send + Sync>),
+    /// No drag image, for rendering a custom overlay instead.
+    Hidden,
+}
+
+impl fmt::Debug for DragImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DragImage::Element(_) => f.write_str("Element"),
+            DragImage::Render(_) => f.write_str("Render"),
+            DragImage::Hidden => f.write_str("Hidden"),
+        }
+    }
+}
+
+/// Sets the drag image of a drag starting from `panel`.
+///
+/// Without a custom image, the native ghost of the panel is used, moved to `offset` if one is given.
+pub(crate) fn set_drag_image(
+    data_transfer: &web_sys::DataTransfer,
+    panel: &web_sys::Element,
+    drag_image: Option<&DragImage>,
+    offset: Option<(i32, i32)>,
+    owner: Option<&Owner>,
+) {
+    let (x, y) = offset.unwrap_or_default();
+    match drag_image {
+        None => {
+            if offset.is_some() {
+                data_transfer.set_drag_image(panel, x, y);
+            }
+        }
+        Some(DragImage::Element(element)) => {
+            if let Some(element) = element() {
+                data_transfer.set_drag_image(&element, x, y);
+            }
+        }
+        Some(DragImage::Render(render)) => {
+            let Some(container) = offscreen_container() else {
+                return;
+            };
+            let view = match owner {
+                Some(owner) => owner.with(|| render()),
+                None => render(),
+            };
+            let mut state = view.build();
+            state.mount(&container, None);
+            data_transfer.set_drag_image(&container, x, y);
+
+            // The browser snapshots the drag image immediately, so it can be removed on the next frame
+            request_animation_frame(move || {
+                state.unmount();
+                container.remove();
+            });
+        }
+        Some(DragImage::Hidden) => {
+            let Some(container) = offscreen_container() else {
+                return;
+            };
+            data_transfer.set_drag_image(&container, 0, 0);
+            request_animation_frame(move || container.remove());
+        }
+    }
+}
+
+/// Appends an element to the body which is rendered outside of the viewport.
+///
+/// Browsers only snapshot drag images which are part of the document.
+fn offscreen_container() -> Option<web_sys::Element> {
+    let document = document();
+    let container = document.create_element("div").ok()?;
+    let _ = container.set_attribute(
+        "style",
+        "position: fixed; top: -10000px; left: -10000px; pointer-events: none;",
+    );
+    document.body()?.append_child(&container).ok()?;
+    Some(container)
+}
@@ -0,0 +1,488 @@
+//! Panel ordering logic, operating on plain columns of panel IDs without signals or the DOM.
+
+use crate::HoverPosition;
+
+/// Where a dragged panel would be dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoverInfo<K> {
+    pub(crate) column_index: usize,
+    /// The panel being hovered, or `None` for the end of the column.
+    pub(crate) panel: Option<HoveredPanel<K>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HoveredPanel<K> {
+    pub(crate) id: K,
+    pub(crate) position: HoverPosition,
+}
+
+/// Returns the `(column_index, index)` of a panel.
+pub(crate) fn position_in<K: PartialEq>(columns: &[Vec<K>], id: &K) -> Option<(usize, usize)> {
+    columns
+        .iter()
+        .enumerate()
+        .find_map(|(column_index, column)| {
+            let index = column.iter().position(|panel_id| panel_id == id)?;
+            Some((column_index, index))
+        })
+}
+
+/// Returns the panels moved by dragging a panel: the whole selection in the order the panels appear if
+/// the dragged panel is selected, or otherwise just the dragged panel.
+pub(crate) fn moved_panels<K: Clone + PartialEq>(
+    columns: &[Vec<K>],
+    dragged: &K,
+    selection: &[K],
+) -> Vec<K> {
+    if selection.contains(dragged) {
+        columns
+            .iter()
+            .flatten()
+            .filter(|panel_id| selection.contains(panel_id))
+            .cloned()
+            .collect()
+    } else {
+        vec![dragged.clone()]
+    }
+}
+
+/// Returns the index moved panels are inserted at among the panels of a column which aren't moving.
+pub(crate) fn insertion_index<K: PartialEq>(
+    column: &[K],
+    moved: &[K],
+    hovered_panel: Option<&HoveredPanel<K>>,
+) -> usize {
+    let remaining_before = |index: usize| {
+        column[..index]
+            .iter()
+            .filter(|panel_id| !moved.contains(panel_id))
+            .count()
+    };
+
+    let Some(HoveredPanel { id, position }) = hovered_panel else {
+        // No hovered panel; insert at the end of the column
+        return remaining_before(column.len());
+    };
+    match column.iter().position(|panel_id| panel_id == id) {
+        // Determine the insertion index based on the hover position
+        Some(index) if !position.is_before() && !moved.contains(id) => remaining_before(index) + 1,
+        Some(index) => remaining_before(index),
+        // If hovered panel is not found, insert at the end
+        None => remaining_before(column.len()),
+    }
+}
+
+/// Returns the `(column_index, index)` positions which move the dragged panel to the hovered position,
+/// along with the rest of the selection if it's selected.
+pub(crate) fn drop_placements<K: Clone + PartialEq>(
+    columns: &[Vec<K>],
+    dragged: &K,
+    selection: &[K],
+    hover_info: &HoverInfo<K>,
+) -> Vec<(K, (usize, usize))> {
+    insert_placements(
+        columns,
+        moved_panels(columns, dragged, selection),
+        hover_info,
+    )
+}
+
+/// Returns the `(column_index, index)` positions which insert panels at the hovered position, keeping
+/// their order. The panels may come from these columns, or from another provider.
+pub(crate) fn insert_placements<K: Clone + PartialEq>(
+    columns: &[Vec<K>],
+    moved: Vec<K>,
+    hover_info: &HoverInfo<K>,
+) -> Vec<(K, (usize, usize))> {
+    let Some(column) = columns.get(hover_info.column_index) else {
+        return Vec::new();
+    };
+
+    let index = insertion_index(column, &moved, hover_info.panel.as_ref());
+    moved
+        .into_iter()
+        .enumerate()
+        .map(|(offset, panel_id)| (panel_id, (hover_info.column_index, index + offset)))
+        .collect()
+}
+
+/// Returns `true` if `can_drop` allows every panel to be placed at its `(column_index, index)` position.
+pub(crate) fn placements_allowed<K>(
+    placements: &[(K, (usize, usize))],
+    mut can_drop: impl FnMut(&K, usize, usize) -> bool,
+) -> bool {
+    placements
+        .iter()
+        .all(|(panel_id, (column_index, index))| can_drop(panel_id, *column_index, *index))
+}
+
+/// Returns the columns with the dragged panel moved to the hovered position, along with the rest of
+/// the selection if it's selected.
+pub(crate) fn reorder<K: Clone + PartialEq>(
+    columns: &[Vec<K>],
+    dragged: &K,
+    selection: &[K],
+    hover_info: &HoverInfo<K>,
+) -> Vec<Vec<K>> {
+    place(
+        columns,
+        &drop_placements(columns, dragged, selection, hover_info),
+    )
+}
+
+/// Returns the placements of panels which exist, into columns which exist.
+pub(crate) fn valid_placements<'a, K: PartialEq>(
+    columns: &[Vec<K>],
+    placements: &'a [(K, (usize, usize))],
+) -> Vec<&'a (K, (usize, usize))> {
+    placements
+        .iter()
+        .filter(|(panel_id, (column_index, _))| {
+            *column_index < columns.len() && position_in(columns, panel_id).is_some()
+        })
+        .collect()
+}
+
+/// Returns the columns with panels moved to new `(column_index, index)` positions.
+///
+/// The panels are removed from the columns, then inserted in order of their new position, so placing
+/// panels at their previous positions reverses a move. Panels which are missing, or placed in a column
+/// which doesn't exist, are left alone.
+pub(crate) fn place<K: Clone + PartialEq>(
+    columns: &[Vec<K>],
+    placements: &[(K, (usize, usize))],
+) -> Vec<Vec<K>> {
+    let mut placements = valid_placements(columns, placements);
+
+    let mut columns = columns.to_vec();
+    for column in &mut columns {
+        column.retain(|panel_id| {
+            !placements
+                .iter()
+                .any(|(placed_id, _)| placed_id == panel_id)
+        });
+    }
+    placements.sort_by_key(|(_, position)| *position);
+    for (panel_id, (column_index, index)) in placements {
+        let column = &mut columns[*column_index];
+        column.insert((*index).min(column.len()), panel_id.clone());
+    }
+
+    columns
+}
+
+/// Returns the hover position to show on the panel `id`, or `None` if it isn't hovered or dropping there
+/// wouldn't move the dragged panel.
+///
+/// `dragged_column` is the column containing the dragged panel, if it's in this provider.
+pub(crate) fn visible_hover_position<K: PartialEq>(
+    id: &K,
+    dragged: &K,
+    dragged_column: Option<&[K]>,
+    hovered_panel: &HoveredPanel<K>,
+) -> Option<HoverPosition> {
+    if hovered_panel.id != *id || dragged == id {
+        return None;
+    }
+
+    // Dropping above the panel below the dragged panel, or below the panel above it, is a no-op
+    let dragged_index = dragged_column.and_then(|column| {
+        let index = column.iter().position(|panel_id| panel_id == dragged)?;
+        Some((column, index))
+    });
+    let hovering_neighbour_panel = match dragged_index {
+        Some((column, index)) if hovered_panel.position.is_before() => {
+            column.get(index + 1) == Some(id)
+        }
+        Some((column, index)) if index > 0 => column.get(index - 1) == Some(id),
+        _ => false,
+    };
+
+    (!hovering_neighbour_panel).then_some(hovered_panel.position)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn hover(column_index: usize, panel: Option<(u32, HoverPosition)>) -> HoverInfo<u32> {
+        HoverInfo {
+            column_index,
+            panel: panel.map(|(id, position)| HoveredPanel { id, position }),
+        }
+    }
+
+    #[test]
+    fn reorder_within_column() {
+        let columns = vec![vec![1, 2, 3, 4]];
+
+        let below = hover(0, Some((3, HoverPosition::Below)));
+        assert_eq!(reorder(&columns, &1, &[], &below), vec![vec![2, 3, 1, 4]]);
+
+        let above = hover(0, Some((1, HoverPosition::Above)));
+        assert_eq!(reorder(&columns, &4, &[], &above), vec![vec![4, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn reorder_across_columns() {
+        let columns = vec![vec![1, 2], vec![3]];
+
+        let above = hover(1, Some((3, HoverPosition::Above)));
+        assert_eq!(
+            reorder(&columns, &2, &[], &above),
+            vec![vec![1], vec![2, 3]]
+        );
+
+        let end = hover(1, None);
+        assert_eq!(reorder(&columns, &1, &[], &end), vec![vec![2], vec![3, 1]]);
+    }
+
+    #[test]
+    fn reorder_selection_keeps_order() {
+        let columns = vec![vec![1, 2, 3], vec![4, 5]];
+        let below = hover(1, Some((4, HoverPosition::Below)));
+        assert_eq!(
+            reorder(&columns, &3, &[3, 1], &below),
+            vec![vec![2], vec![4, 1, 3, 5]]
+        );
+    }
+
+    #[test]
+    fn selected_panels_are_each_checked() {
+        let columns = vec![vec![1, 2, 3], vec![4]];
+        let end = hover(1, None);
+
+        let placements = drop_placements(&columns, &2, &[2, 1], &end);
+        assert_eq!(placements, vec![(1, (1, 1)), (2, (1, 2))]);
+
+        // Panel 1 can't leave its column, so dragging the selection with panel 2 is disallowed
+        let keep_1_in_column_0 = |id: &u32, column_index, _| *id != 1 || column_index == 0;
+        assert!(!placements_allowed(&placements, keep_1_in_column_0));
+        let placements = drop_placements(&columns, &2, &[], &end);
+        assert!(placements_allowed(&placements, keep_1_in_column_0));
+    }
+
+    #[test]
+    fn insert_panels_from_another_provider() {
+        let columns = vec![vec![1, 2], vec![3]];
+
+        let above = hover(0, Some((2, HoverPosition::Above)));
+        assert_eq!(
+            insert_placements(&columns, vec![7, 8], &above),
+            vec![(7, (0, 1)), (8, (0, 2))]
+        );
+        assert_eq!(
+            insert_placements(&columns, vec![7], &hover(1, None)),
+            vec![(7, (1, 1))]
+        );
+        assert!(insert_placements(&columns, vec![7], &hover(2, None)).is_empty());
+    }
+
+    #[test]
+    fn reorder_unselected_panel_ignores_selection() {
+        let columns = vec![vec![1, 2, 3]];
+        let end = hover(0, None);
+        assert_eq!(reorder(&columns, &1, &[2], &end), vec![vec![2, 3, 1]]);
+    }
+
+    #[test]
+    fn reorder_missing_panel_or_column_is_noop() {
+        let columns = vec![vec![1, 2]];
+        assert_eq!(reorder(&columns, &9, &[], &hover(0, None)), columns);
+        assert_eq!(reorder(&columns, &1, &[], &hover(3, None)), columns);
+    }
+
+    #[test]
+    fn place_reverses_move() {
+        let columns = vec![vec![1, 2, 3], vec![4]];
+        let moved = place(&columns, &[(2, (1, 0)), (3, (1, 2))]);
+        assert_eq!(moved, vec![vec![1], vec![2, 4, 3]]);
+        assert_eq!(place(&moved, &[(2, (0, 1)), (3, (0, 2))]), columns);
+    }
+
+    #[test]
+    fn neighbour_hover_positions_are_hidden() {
+        let column = [1, 2, 3];
+        let hovered = |id, position| HoveredPanel { id, position };
+
+        let above_next = hovered(3, HoverPosition::Above);
+        assert_eq!(
+            visible_hover_position(&3, &2, Some(&column), &above_next),
+            None
+        );
+        let below_previous = hovered(1, HoverPosition::Below);
+        assert_eq!(
+            visible_hover_position(&1, &2, Some(&column), &below_previous),
+            None
+        );
+        let over_itself = hovered(2, HoverPosition::Above);
+        assert_eq!(
+            visible_hover_position(&2, &2, Some(&column), &over_itself),
+            None
+        );
+
+        let below_next = hovered(3, HoverPosition::Below);
+        assert_eq!(
+            visible_hover_position(&3, &2, Some(&column), &below_next),
+            Some(HoverPosition::Below)
+        );
+        assert_eq!(
+            visible_hover_position(&1, &2, Some(&column), &below_next),
+            None
+        );
+    }
+
+    /// Columns of unique panel IDs.
+    fn columns() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        prop::collection::vec(prop::collection::vec(any::<u8>(), 0..6), 1..5).prop_map(|columns| {
+            let mut next_id = 0;
+            columns
+                .into_iter()
+                .map(|column| {
+                    column
+                        .into_iter()
+                        .map(|_| {
+                            next_id += 1;
+                            next_id
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+    }
+
+    fn hover_position() -> impl Strategy<Value = HoverPosition> {
+        prop_oneof![
+            Just(HoverPosition::Above),
+            Just(HoverPosition::Below),
+            Just(HoverPosition::Left),
+            Just(HoverPosition::Right),
+        ]
+    }
+
+    /// Columns with a dragged panel, a selection, and a hover target.
+    fn drag() -> impl Strategy<Value = (Vec<Vec<u32>>, u32, Vec<u32>, HoverInfo<u32>)> {
+        columns()
+            .prop_filter("needs a panel to drag", |columns| {
+                columns.iter().any(|column| !column.is_empty())
+            })
+            .prop_flat_map(|columns| {
+                let ids = columns.iter().flatten().copied().collect::<Vec<_>>();
+                let column_count = columns.len();
+                (
+                    Just(columns),
+                    prop::sample::select(ids.clone()),
+                    prop::sample::subsequence(ids.clone(), 0..=ids.len()),
+                    0..column_count,
+                    prop::option::of((prop::sample::select(ids), hover_position())),
+                )
+            })
+            .prop_map(|(columns, dragged, selection, column_index, panel)| {
+                // Only hover panels in the hovered column
+                let panel = panel.filter(|(id, _)| columns[column_index].contains(id));
+                (columns, dragged, selection, hover(column_index, panel))
+            })
+    }
+
+    fn sorted_ids(columns: &[Vec<u32>]) -> Vec<u32> {
+        let mut ids = columns.iter().flatten().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
+    proptest! {
+        #[test]
+        fn reorder_keeps_every_panel_once((columns, dragged, selection, hover_info) in drag()) {
+            let reordered = reorder(&columns, &dragged, &selection, &hover_info);
+
+            prop_assert_eq!(reordered.len(), columns.len());
+            let ids = reordered.iter().flatten().collect::<Vec<_>>();
+            prop_assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len(), "duplicate ids");
+            prop_assert_eq!(sorted_ids(&reordered), sorted_ids(&columns), "lost ids");
+        }
+
+        #[test]
+        fn reorder_moves_panels_to_hovered_column((columns, dragged, selection, hover_info) in drag()) {
+            let reordered = reorder(&columns, &dragged, &selection, &hover_info);
+
+            for id in moved_panels(&columns, &dragged, &selection) {
+                prop_assert_eq!(
+                    position_in(&reordered, &id).map(|(column_index, _)| column_index),
+                    Some(hover_info.column_index)
+                );
+            }
+        }
+
+        #[test]
+        fn reorder_selection_stays_together((columns, dragged, selection, hover_info) in drag()) {
+            let moved = moved_panels(&columns, &dragged, &selection);
+            let reordered = reorder(&columns, &dragged, &selection, &hover_info);
+
+            let column = &reordered[hover_info.column_index];
+            let start = column.iter().position(|id| *id == moved[0]).unwrap();
+            prop_assert_eq!(&column[start..start + moved.len()], moved.as_slice());
+        }
+
+        #[test]
+        fn drop_next_to_itself_is_noop(
+            (columns, dragged) in columns()
+                .prop_filter("needs a panel to drag", |columns| {
+                    columns.iter().any(|column| !column.is_empty())
+                })
+                .prop_flat_map(|columns| {
+                    let ids = columns.iter().flatten().copied().collect::<Vec<_>>();
+                    (Just(columns), prop::sample::select(ids))
+                }),
+            position in hover_position(),
+        ) {
+            let (column_index, index) = position_in(&columns, &dragged).unwrap();
+            let column = &columns[column_index];
+
+            let mut targets = vec![Some((dragged, position))];
+            if let Some(next) = column.get(index + 1) {
+                targets.push(Some((*next, HoverPosition::Above)));
+                targets.push(Some((*next, HoverPosition::Left)));
+            }
+            if let Some(previous) = index.checked_sub(1).map(|index| column[index]) {
+                targets.push(Some((previous, HoverPosition::Below)));
+                targets.push(Some((previous, HoverPosition::Right)));
+            }
+            if index == column.len() - 1 {
+                targets.push(None);
+            }
+
+            for panel in targets {
+                let hover_info = hover(column_index, panel);
+                prop_assert_eq!(&reorder(&columns, &dragged, &[], &hover_info), &columns);
+            }
+        }
+
+        #[test]
+        fn panels_land_at_their_placements((columns, dragged, selection, hover_info) in drag()) {
+            let placements = drop_placements(&columns, &dragged, &selection, &hover_info);
+            let reordered = place(&columns, &placements);
+
+            for (id, position) in placements {
+                prop_assert_eq!(position_in(&reordered, &id), Some(position));
+            }
+        }
+
+        #[test]
+        fn placing_at_previous_positions_reverses((columns, dragged, selection, hover_info) in drag()) {
+            let reordered = reorder(&columns, &dragged, &selection, &hover_info);
+
+            let placements = moved_panels(&columns, &dragged, &selection)
+                .into_iter()
+                .map(|id| {
+                    let position = position_in(&columns, &id).unwrap();
+                    (id, position)
+                })
+                .collect::<Vec<_>>();
+            prop_assert_eq!(place(&reordered, &placements), columns);
+        }
+    }
+}
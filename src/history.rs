@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+
+/// A panel moved from one position to another, as `(column_index, index)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PanelMove<K> {
+    pub(crate) id: K,
+    pub(crate) from: (usize, usize),
+    pub(crate) to: (usize, usize),
+}
+
+/// A committed change to the panel order, which can be reversed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum HistoryEntry<K> {
+    /// One or more panels moved to new positions.
+    Moved(Vec<PanelMove<K>>),
+    /// A panel removed from a position.
+    Removed { id: K, position: (usize, usize) },
+    /// A removed panel put back at its position.
+    Restored { id: K, position: (usize, usize) },
+}
+
+impl<K> HistoryEntry<K> {
+    /// Returns the change which reverses this one.
+    pub(crate) fn reversed(self) -> Self {
+        match self {
+            HistoryEntry::Moved(moves) => HistoryEntry::Moved(
+                moves
+                    .into_iter()
+                    .map(|PanelMove { id, from, to }| PanelMove {
+                        id,
+                        from: to,
+                        to: from,
+                    })
+                    .collect(),
+            ),
+            HistoryEntry::Removed { id, position } => HistoryEntry::Restored { id, position },
+            HistoryEntry::Restored { id, position } => HistoryEntry::Removed { id, position },
+        }
+    }
+}
+
+/// Undo and redo stacks of committed changes.
+#[derive(Clone, Debug)]
+pub(crate) struct History<K> {
+    undo: VecDeque<HistoryEntry<K>>,
+    redo: Vec<HistoryEntry<K>>,
+    depth: usize,
+}
+
+impl<K> History<K> {
+    pub(crate) fn new(depth: usize) -> Self {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Records a new move, forgetting the oldest move once the depth is exceeded and clearing the
+    /// redo stack.
+    pub(crate) fn record(&mut self, entry: HistoryEntry<K>) {
+        self.redo.clear();
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    /// Takes the most recent move to be undone, moving it onto the redo stack.
+    pub(crate) fn undo(&mut self) -> Option<HistoryEntry<K>>
+    where
+        K: Clone,
+    {
+        let entry = self.undo.pop_back()?;
+        self.redo.push(entry.clone());
+        Some(entry)
+    }
+
+    /// Takes the most recently undone move to be redone, moving it back onto the undo stack.
+    pub(crate) fn redo(&mut self) -> Option<HistoryEntry<K>>
+    where
+        K: Clone,
+    {
+        let entry = self.redo.pop()?;
+        self.undo.push_back(entry.clone());
+        Some(entry)
+    }
+
+    /// Forgets every move, such as when the positions they were recorded with no longer apply.
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
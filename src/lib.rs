@@ -1,25 +1,76 @@
 #![doc = include_str!("../README.md")]
 
-use std::collections::HashMap;
+mod animation;
+mod announcer;
+mod auto_scroll;
+mod drag_image;
+mod engine;
+mod history;
+#[cfg(feature = "persist")]
+mod persist;
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
 
 use js_sys::Function;
 use leptos::{ev, html::ElementType, prelude::*, tachys::dom::event_target};
 use send_wrapper::SendWrapper;
 use wasm_bindgen::{prelude::Closure, JsCast};
 
+pub use animation::ReorderAnimation;
+use animation::{animate_reorder, PanelElements};
+use announcer::Announcer;
+pub use announcer::{Announcement, AnnouncementKind};
+pub use auto_scroll::AutoScroll;
+use auto_scroll::AutoScroller;
+use drag_image::{set_drag_image, DragImage};
+use engine::{position_in, HoverInfo, HoveredPanel};
+use history::{History, HistoryEntry, PanelMove};
+#[cfg(feature = "persist")]
+use persist::Persistence;
+#[cfg(feature = "persist")]
+pub use persist::{Persist, PersistStorage};
+
+/// A type which can be used to identify panels.
+///
+/// Implemented for any cloneable, hashable type, such as `u32`, `Uuid`, `String` or `Oco<'static, str>`.
+/// Panels and their provider must use the same ID type.
+pub trait PanelId: Clone + Eq + Hash + Send + Sync + 'static {}
+
+impl<T> PanelId for T where T: Clone + Eq + Hash + Send + Sync + 'static {}
+
 /// Return value for [`use_drag_reorder`].
-pub struct UseDragReorderReturn<E, SetDraggable, OnDragStart, OnDragEnd>
-where
+pub struct UseDragReorderReturn<
+    E,
+    SetDraggable,
+    OnDragStart,
+    OnDragEnd,
+    OnPointerDown,
+    OnKeyDown,
+    SetSelected,
+    OnClick,
+> where
     E: ElementType,
     E::Output: 'static,
     SetDraggable: Fn(bool) + Copy,
     OnDragStart: Fn(ev::DragEvent) + Clone,
     OnDragEnd: Fn(ev::DragEvent) + Clone,
+    OnPointerDown: Fn(ev::PointerEvent) + Clone,
+    OnKeyDown: Fn(ev::KeyboardEvent) + Clone,
+    SetSelected: Fn(bool) + Copy,
+    OnClick: Fn(ev::MouseEvent) + Clone,
 {
     /// Node ref which should be assigned to the panel element.
     pub node_ref: NodeRef<E>,
-    /// Is this panel being dragged.
+    /// Is this panel being dragged, either directly or as part of the dragged selection.
     pub is_dragging: Signal<bool>,
+    /// Is this panel picked up with the keyboard.
+    pub is_keyboard_grabbed: Signal<bool>,
     /// The current position this panel is being hovered over.
     ///
     /// This is useful for styling. Typically you would have a line above or below this panel to indicate
@@ -33,46 +84,140 @@ where
     pub on_dragstart: OnDragStart,
     /// Callback which should be assigned to the `on:dragend` event.
     pub on_dragend: OnDragEnd,
+    /// Callback which should be assigned to the `on:pointerdown` event.
+    ///
+    /// This is only used by the [`DragBackend::Pointer`] backend, and does nothing otherwise.
+    pub on_pointerdown: OnPointerDown,
+    /// Callback which should be assigned to the `on:keydown` event.
+    ///
+    /// When the panel is focused, Space or Enter picks it up, the arrow keys move it within and across
+    /// columns, Enter or Space drops it, and Escape cancels. The panel needs a `tabindex` to be focusable.
+    pub on_keydown: OnKeyDown,
+    /// Is this panel selected.
+    ///
+    /// Dragging a selected panel moves every selected panel of the provider, keeping their order.
+    pub is_selected: Signal<bool>,
+    /// Selects/deselects the panel.
+    pub set_selected: SetSelected,
+    /// Callback which should be assigned to the `on:click` event.
+    ///
+    /// Ctrl or Cmd clicking toggles the selection of the panel, and Shift clicking selects every panel
+    /// between it and the last toggled panel.
+    pub on_click: OnClick,
 }
 
-/// A hovering panels position either above or below.
+/// A hovering panels position either above or below, or left or right in a [`Layout::Horizontal`]
+/// or [`Layout::Grid`] column.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HoverPosition {
     Above,
     Below,
+    Left,
+    Right,
+}
+
+impl HoverPosition {
+    /// Returns `true` if the dragged panel would be inserted before the hovered panel.
+    pub fn is_before(self) -> bool {
+        matches!(self, HoverPosition::Above | HoverPosition::Left)
+    }
+}
+
+/// The direction panels are laid out in a column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// Panels are stacked top to bottom, hovering [`HoverPosition::Above`] or [`HoverPosition::Below`].
+    #[default]
+    Vertical,
+    /// Panels are placed left to right, such as in a tab strip, hovering [`HoverPosition::Left`]
+    /// or [`HoverPosition::Right`].
+    Horizontal,
+    /// Panels wrap into rows, such as with `flex-wrap`, and are ordered left to right then top to bottom.
+    ///
+    /// The closest panel is found by distance in both directions, hovering [`HoverPosition::Left`]
+    /// if the dragged panel comes before it in reading order, or [`HoverPosition::Right`] if after.
+    Grid,
+}
+
+impl Layout {
+    /// Returns the hover positions before and after a panel in this layout.
+    fn hover_positions(self) -> (HoverPosition, HoverPosition) {
+        match self {
+            Layout::Vertical => (HoverPosition::Above, HoverPosition::Below),
+            Layout::Horizontal | Layout::Grid => (HoverPosition::Left, HoverPosition::Right),
+        }
+    }
 }
 
 /// Registers a panel with drag reordering for a given ID.
-pub fn use_drag_reorder<E>(
-    id: impl Into<Oco<'static, str>>,
+#[allow(clippy::type_complexity)]
+pub fn use_drag_reorder<E, K>(
+    id: K,
+) -> UseDragReorderReturn<
+    E,
+    impl Fn(bool) + Copy,
+    impl Fn(ev::DragEvent) + Clone,
+    impl Fn(ev::DragEvent) + Clone,
+    impl Fn(ev::PointerEvent) + Clone,
+    impl Fn(ev::KeyboardEvent) + Clone,
+    impl Fn(bool) + Copy,
+    impl Fn(ev::MouseEvent) + Clone,
+>
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
+{
+    use_drag_reorder_with_options(id, UseDragReorderOptions::default())
+}
+
+/// Version of [`use_drag_reorder`] that takes [`UseDragReorderOptions`].
+#[allow(clippy::type_complexity)]
+pub fn use_drag_reorder_with_options<E, K>(
+    id: K,
+    options: UseDragReorderOptions,
 ) -> UseDragReorderReturn<
     E,
     impl Fn(bool) + Copy,
     impl Fn(ev::DragEvent) + Clone,
     impl Fn(ev::DragEvent) + Clone,
+    impl Fn(ev::PointerEvent) + Clone,
+    impl Fn(ev::KeyboardEvent) + Clone,
+    impl Fn(bool) + Copy,
+    impl Fn(ev::MouseEvent) + Clone,
 >
 where
     E: ElementType + 'static,
     E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
 {
+    let UseDragReorderOptions {
+        scope,
+        drag_image,
+        drag_image_offset,
+    } = options;
+
+    let ctx = use_drag_reorder_context::<K>(scope.as_deref());
     let DragReorderContext {
-        column_refs,
         panel_order,
         currently_dragged_panel,
         hover_info,
+        incoming_panel,
         panels,
-    } = expect_context();
-    let mut id: Oco<'static, str> = id.into();
-    id.upgrade_inplace();
+        keyboard_dragging,
+        backend,
+        selection,
+        ..
+    } = ctx.clone();
     let node_ref = NodeRef::<E>::new();
 
     Effect::new({
         let id = id.clone();
         move |_| match node_ref.get() {
             Some(node_ref) => {
-                panels
-                    .write()
-                    .insert(id.clone(), SendWrapper::new(node_ref.into()));
+                let panel: web_sys::Element = node_ref.into();
+                let _ = panel.set_attribute(PANEL_ATTRIBUTE, "");
+                panels.write().insert(id.clone(), SendWrapper::new(panel));
             }
             None => {
                 panels.write().remove(&id);
@@ -87,67 +232,46 @@ where
         }
     });
 
-    let is_dragging = Signal::derive({
+    let is_selected = Signal::derive({
+        let id = id.clone();
+        move || selection.read().contains(&id)
+    });
+    let is_dragged = Signal::derive({
         let id = id.clone();
-        move || currently_dragged_panel.read().as_deref() == Some(id.as_str())
+        move || currently_dragged_panel.read().as_ref() == Some(&id)
+    });
+    let is_dragging = Signal::derive(move || {
+        is_dragged.get()
+            || (is_selected.get()
+                && currently_dragged_panel
+                    .read()
+                    .as_ref()
+                    .is_some_and(|dragged| selection.read().contains(dragged)))
     });
+    let is_keyboard_grabbed = Signal::derive(move || keyboard_dragging.get() && is_dragged.get());
     let hover_position = Signal::derive({
         let id = id.clone();
-        let panel_order = panel_order.clone();
         move || match &*hover_info.read() {
             Some(HoverInfo {
                 panel: Some(panel), ..
             }) => {
                 let currently_dragged_panel = currently_dragged_panel.read();
-                let Some(currently_dragged_panel) = &*currently_dragged_panel else {
-                    return None;
-                };
+                let incoming_panel = incoming_panel.read();
+                let currently_dragged_panel = currently_dragged_panel
+                    .as_ref()
+                    .or(incoming_panel.as_ref())?;
 
-                let hovering_this_panel = panel.id == id.as_str();
-                let is_currently_dragged_panel = currently_dragged_panel == id.as_str();
-
-                let currently_dragged_panel_index =
-                    panel_order
-                        .iter()
-                        .enumerate()
-                        .find_map(|(column_index, column)| {
-                            column
-                                .read()
-                                .iter()
-                                .position(|panel_id| panel_id == currently_dragged_panel)
-                                .map(|pos| (column_index, pos))
-                        });
-                let hovering_neighbour_panel = match (currently_dragged_panel_index, panel.position)
-                {
-                    (Some((column_index, panel_index)), HoverPosition::Above) => panel_order
-                        .get(column_index)
-                        .and_then(|column| {
-                            column
-                                .read()
-                                .get(panel_index + 1)
-                                .map(|below_id| below_id.as_str() == id)
-                        })
-                        .unwrap_or(false),
-                    (Some((column_index, panel_index)), HoverPosition::Below)
-                        if panel_index > 0 =>
-                    {
-                        panel_order
-                            .get(column_index)
-                            .and_then(|column| {
-                                column
-                                    .read()
-                                    .get(panel_index - 1)
-                                    .map(|below_id| below_id.as_str() == id)
-                            })
-                            .unwrap_or(false)
-                    }
-                    _ => false,
-                };
-                if hovering_this_panel && !is_currently_dragged_panel && !hovering_neighbour_panel {
-                    Some(panel.position)
-                } else {
-                    None
-                }
+                let panel_order = panel_order.read();
+                let dragged_column = panel_order
+                    .iter()
+                    .map(|column| column.read())
+                    .find(|column| column.contains(currently_dragged_panel));
+                engine::visible_hover_position(
+                    &id,
+                    currently_dragged_panel,
+                    dragged_column.as_deref().map(Vec::as_slice),
+                    panel,
+                )
             }
             _ => None,
         }
@@ -158,343 +282,2386 @@ where
         draggable.set(can_drag);
     };
 
-    let on_dragover_cb: RwSignal<Option<Function>, LocalStorage> = RwSignal::new_local(None);
+    let handle = RwSignal::new_local(None);
+    provide_context(DragHandleContext { draggable, handle });
 
+    let owner = Owner::current();
     let on_drag_start = {
         let id = id.clone();
+        let ctx = ctx.clone();
         move |ev: ev::DragEvent| {
+            if backend != DragBackend::Html5 {
+                // Native dragging would steal the pointer events from the pointer backend
+                ev.prevent_default();
+                return;
+            }
+            if !is_innermost_panel(&ev, node_ref) {
+                return;
+            }
+
+            keyboard_dragging.set(false);
             currently_dragged_panel.set(Some(id.clone()));
 
             let dragged_el = event_target::<web_sys::HtmlElement>(&ev);
-            let mouse_x = ev.client_x() as f64;
-            let mouse_y = ev.client_y() as f64;
-            let rect = dragged_el.get_bounding_client_rect();
+            ctx.drag_offset.set_value(center_offset(
+                &dragged_el,
+                ev.client_x() as f64,
+                ev.client_y() as f64,
+            ));
 
-            // Calculate the center of the element
-            let center_x = rect.x() + rect.width() / 2.0;
-            let center_y = rect.y() + rect.height() / 2.0;
-
-            // Calculate the offset from the mouse position to the center of the element
-            let offset_x = mouse_x - center_x;
-            let offset_y = mouse_y - center_y;
+            // The browser only fires `drop` for an accepted drop, so a drag which ends without one was
+            // cancelled. `dragend` no longer reaches the document once the dragged element is removed,
+            // such as by a live reorder, so it's listened for on the element itself.
+            let on_dragend: Function = Closure::once_into_js({
+                let ctx = ctx.clone();
+                move || {
+                    if ctx.is_native_dragging() {
+                        ctx.cancel_drag();
+                    }
+                }
+            })
+            .unchecked_into();
+            let options = web_sys::AddEventListenerOptions::new();
+            options.set_once(true);
+            let _ = dragged_el.add_event_listener_with_callback_and_add_event_listener_options(
+                "dragend",
+                &on_dragend,
+                &options,
+            );
 
             // Necessary for firefox to emit drag events
             if let Some(data_transfer) = ev.data_transfer() {
-                let _ = data_transfer.set_data("text/plain", &id);
+                let _ = data_transfer.set_data("text/plain", "");
+                set_drag_image(
+                    &data_transfer,
+                    &dragged_el,
+                    drag_image.as_ref(),
+                    drag_image_offset,
+                    owner.as_ref(),
+                );
             }
+        }
+    };
 
-            let column_refs = column_refs.clone();
-            let panel_order = panel_order.clone();
-            let on_dragover: Function = Closure::wrap(Box::new(move |ev: web_sys::DragEvent| {
-                ev.prevent_default();
+    let on_drag_end = {
+        let id = id.clone();
+        let ctx = ctx.clone();
+        move |_ev: ev::DragEvent| {
+            let id = id.clone();
+            let ctx = ctx.clone();
+            request_animation_frame(move || {
+                draggable.set(false);
+                if currently_dragged_panel.read_untracked().as_ref() == Some(&id) {
+                    ctx.end_drag();
+                }
+            });
+        }
+    };
 
-                let mouse_x = ev.client_x() as f64 - offset_x;
-                let mouse_y = ev.client_y() as f64 - offset_y;
-
-                let (closest_column, _) = column_refs.iter().enumerate().fold(
-                    (None, f64::INFINITY),
-                    |(column, closest_dist), (i, column_ref)| {
-                        let Some(column_ref) = &*column_ref.read_untracked() else {
-                            return (column, closest_dist);
-                        };
-                        let rect = column_ref.get_bounding_client_rect();
-                        let center_x = rect.left() + rect.width() / 2.0;
-                        let dist = (mouse_x - center_x).abs();
-                        if dist < closest_dist {
-                            (Some((i, column_ref.clone())), dist)
-                        } else {
-                            (column, closest_dist)
-                        }
-                    },
-                );
+    let pointer_listeners: RwSignal<Vec<(&'static str, Function)>, LocalStorage> =
+        RwSignal::new_local(Vec::new());
+    let remove_pointer_listeners = move || {
+        for (event, listener) in pointer_listeners.write().drain(..) {
+            let _ = document().remove_event_listener_with_callback(event, &listener);
+        }
+    };
 
-                if let Some((column_index, _)) = closest_column {
-                    let (closest_panel, _) = panels.read_untracked().iter().fold(
-                        (None, f64::INFINITY),
-                        |(closest_panel, closest_dist), (panel_id, panel_ref)| {
-                            let is_in_column = panel_order
-                                .get(column_index)
-                                .map(|column_panels| {
-                                    column_panels.read_untracked().contains(panel_id)
-                                })
-                                .unwrap_or(false);
-                            if !is_in_column {
-                                return (closest_panel, closest_dist);
-                            }
+    let on_pointer_down = {
+        let id = id.clone();
+        let ctx = ctx.clone();
+        move |ev: ev::PointerEvent| {
+            if backend != DragBackend::Pointer || !ev.is_primary() || ev.button() != 0 {
+                return;
+            }
+            if !is_innermost_panel(&ev, node_ref) || !is_on_handle(&ev, handle) {
+                return;
+            }
+            let Some(el) = node_ref.get_untracked() else {
+                return;
+            };
 
-                            let rect = panel_ref.get_bounding_client_rect();
-                            let center_y = rect.top() + rect.height() / 2.0;
-                            let dist = (mouse_y - center_y).abs();
-                            if dist < closest_dist {
-                                (Some((panel_id.clone(), panel_ref.clone(), center_y)), dist)
-                            } else {
-                                (closest_panel, closest_dist)
-                            }
-                        },
-                    );
-
-                    let new_hover_info = if let Some((panel_id, _, center_y)) = closest_panel {
-                        if mouse_y < center_y {
-                            Some(HoverInfo {
-                                column_index,
-                                panel: Some(HoveredPanel {
-                                    id: panel_id,
-                                    position: HoverPosition::Above,
-                                }),
-                            })
-                        } else {
-                            Some(HoverInfo {
-                                column_index,
-                                panel: Some(HoveredPanel {
-                                    id: panel_id,
-                                    position: HoverPosition::Below,
-                                }),
-                            })
+            remove_pointer_listeners();
+
+            let start_x = ev.client_x() as f64;
+            let start_y = ev.client_y() as f64;
+            let (offset_x, offset_y) = center_offset(&el.into(), start_x, start_y);
+
+            let on_pointermove: Function = Closure::wrap(Box::new({
+                let id = id.clone();
+                let ctx = ctx.clone();
+                move |ev: web_sys::PointerEvent| {
+                    let mouse_x = ev.client_x() as f64;
+                    let mouse_y = ev.client_y() as f64;
+                    if currently_dragged_panel.read_untracked().as_ref() != Some(&id) {
+                        // Don't treat small movements (eg. clicks) as drags
+                        if (mouse_x - start_x).hypot(mouse_y - start_y) < POINTER_DRAG_THRESHOLD {
+                            return;
                         }
-                    } else {
-                        Some(HoverInfo {
-                            column_index,
-                            panel: None,
-                        })
-                    };
+                        keyboard_dragging.set(false);
+                        currently_dragged_panel.set(Some(id.clone()));
+                    }
 
-                    hover_info.maybe_update(move |hovered| {
-                        if hovered != &new_hover_info {
-                            *hovered = new_hover_info;
-                            true
+                    ev.prevent_default();
+                    ctx.auto_scroller.update(ctx.column_refs, mouse_x, mouse_y);
+                    ctx.update_hover_info(mouse_x, mouse_y, (offset_x, offset_y));
+                }
+            }) as Box<dyn FnMut(_)>)
+            .into_js_value()
+            .dyn_into()
+            .unwrap();
+
+            let on_pointerup: Function = Closure::wrap(Box::new({
+                let id = id.clone();
+                let ctx = ctx.clone();
+                move |ev: web_sys::PointerEvent| {
+                    remove_pointer_listeners();
+
+                    if currently_dragged_panel.read_untracked().as_ref() == Some(&id) {
+                        if ev.type_() == "pointerup" {
+                            ctx.drop_dragged_panel();
+                            ctx.end_drag();
                         } else {
-                            false
+                            ctx.cancel_drag();
                         }
-                    });
+                    }
                 }
             }) as Box<dyn FnMut(_)>)
             .into_js_value()
             .dyn_into()
             .unwrap();
 
-            document()
-                .add_event_listener_with_callback_and_bool("dragover", &on_dragover, false)
-                .unwrap();
+            let on_keydown: Function = Closure::wrap(Box::new({
+                let id = id.clone();
+                let ctx = ctx.clone();
+                move |ev: web_sys::KeyboardEvent| {
+                    if ev.key() != "Escape"
+                        || currently_dragged_panel.read_untracked().as_ref() != Some(&id)
+                    {
+                        return;
+                    }
+                    ev.prevent_default();
+                    remove_pointer_listeners();
+                    ctx.cancel_drag();
+                }
+            }) as Box<dyn FnMut(_)>)
+            .into_js_value()
+            .dyn_into()
+            .unwrap();
 
-            on_dragover_cb.set(Some(on_dragover));
+            let document = document();
+            for (event, listener) in [
+                ("pointermove", on_pointermove),
+                ("pointerup", on_pointerup.clone()),
+                ("pointercancel", on_pointerup),
+                ("keydown", on_keydown),
+            ] {
+                document
+                    .add_event_listener_with_callback(event, &listener)
+                    .unwrap();
+                pointer_listeners.write().push((event, listener));
+            }
         }
     };
 
-    let on_drag_end = {
+    on_cleanup(remove_pointer_listeners);
+
+    let on_key_down = {
         let id = id.clone();
-        move |_ev: ev::DragEvent| {
-            if let Some(on_dragover) = on_dragover_cb.write().take() {
-                document()
-                    .remove_event_listener_with_callback("dragover", &on_dragover)
-                    .unwrap();
+        let ctx = ctx.clone();
+        move |ev: ev::KeyboardEvent| {
+            let is_grabbed = keyboard_dragging.get_untracked()
+                && currently_dragged_panel.read_untracked().as_ref() == Some(&id);
+            if !is_grabbed {
+                // Ignore key presses from inputs inside the panel
+                let is_panel_focused = ev.target() == ev.current_target();
+                if matches!(ev.key().as_str(), " " | "Enter")
+                    && is_panel_focused
+                    && currently_dragged_panel.read_untracked().is_none()
+                {
+                    ev.prevent_default();
+                    ctx.start_keyboard_drag(id.clone());
+                }
+                return;
             }
 
-            let id = id.clone();
-            request_animation_frame(move || {
-                let mut current = currently_dragged_panel.write();
-                if current.as_deref() == Some(&id) {
-                    hover_info.set(None);
-                    draggable.set(false);
-                    *current = None;
+            match ev.key().as_str() {
+                "ArrowUp" => ctx.move_keyboard_drag(0, -1),
+                "ArrowDown" => ctx.move_keyboard_drag(0, 1),
+                "ArrowLeft" => ctx.move_keyboard_drag(-1, 0),
+                "ArrowRight" => ctx.move_keyboard_drag(1, 0),
+                " " | "Enter" => {
+                    ctx.drop_dragged_panel();
+                    ctx.end_drag();
+
+                    // Moving to another column remounts the panel, so focus has to be restored
+                    let id = id.clone();
+                    request_animation_frame(move || {
+                        if let Some(panel) = panels.read_untracked().get(&id) {
+                            if let Some(panel) = panel.dyn_ref::<web_sys::HtmlElement>() {
+                                let _ = panel.focus();
+                            }
+                        }
+                    });
                 }
-            });
+                "Escape" => ctx.cancel_drag(),
+                "Tab" => {
+                    ctx.cancel_drag();
+                    return;
+                }
+                _ => return,
+            }
+            ev.prevent_default();
+        }
+    };
+
+    let set_selected = {
+        let id = StoredValue::new(id.clone());
+        let ctx = StoredValue::new(ctx.clone());
+        move |selected: bool| {
+            ctx.with_value(|ctx| id.with_value(|id| ctx.set_selected(id, selected)));
+        }
+    };
+
+    let on_click = {
+        let id = id.clone();
+        let ctx = ctx.clone();
+        move |ev: ev::MouseEvent| {
+            if !is_innermost_panel(&ev, node_ref) {
+                return;
+            }
+            if ev.shift_key() {
+                ev.prevent_default();
+                ctx.select_range(&id);
+            } else if ev.ctrl_key() || ev.meta_key() {
+                ev.prevent_default();
+                ctx.set_selected(&id, !ctx.selection.read_untracked().contains(&id));
+            }
         }
     };
 
     UseDragReorderReturn {
         node_ref,
         is_dragging,
+        is_keyboard_grabbed,
         hover_position,
         draggable: draggable.into(),
         set_draggable,
         on_dragstart: on_drag_start,
         on_dragend: on_drag_end,
+        on_pointerdown: on_pointer_down,
+        on_keydown: on_key_down,
+        is_selected,
+        set_selected,
+        on_click,
     }
 }
 
-#[derive(Clone)]
-struct DragReorderContext {
-    column_refs: Vec<Signal<Option<SendWrapper<web_sys::Element>>>>,
-    panel_order: Vec<RwSignal<Vec<Oco<'static, str>>>>,
-    currently_dragged_panel: RwSignal<Option<Oco<'static, str>>>,
-    hover_info: RwSignal<Option<HoverInfo>>,
-    panels: RwSignal<HashMap<Oco<'static, str>, SendWrapper<web_sys::Element>>>,
+/// Drag handle state of the panel provided by [`use_drag_reorder`].
+#[derive(Clone, Copy)]
+struct DragHandleContext {
+    draggable: RwSignal<bool>,
+    handle: RwSignal<Option<web_sys::Element>, LocalStorage>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct HoverInfo {
-    column_index: usize,
-    panel: Option<HoveredPanel>,
+/// Returns a node ref for a drag handle of the nearest panel, which must be called within a component using
+/// [`use_drag_reorder`].
+///
+/// Once the handle is mounted, the panel is only draggable while the pointer is pressed on the handle,
+/// instead of requiring `set_draggable` to be called. This keeps text selection and inputs inside the panel
+/// working.
+pub fn use_drag_handle<E>() -> NodeRef<E>
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+{
+    let DragHandleContext { draggable, handle } = expect_context();
+    let handle_ref = NodeRef::<E>::new();
+
+    Effect::new(move |_| {
+        handle.set(handle_ref.get().map(Into::into));
+    });
+
+    // Listeners are attached in an effect so they're only added on the client
+    Effect::new(move |_| {
+        let listeners = [
+            window_event_listener(ev::pointerdown, move |ev| {
+                if handle.read_untracked().is_some() {
+                    draggable.set(ev.is_primary() && ev.button() == 0 && is_on_handle(&ev, handle));
+                }
+            }),
+            window_event_listener(ev::pointerup, move |_| draggable.set(false)),
+            window_event_listener(ev::pointercancel, move |_| draggable.set(false)),
+            window_event_listener(ev::blur, move |_| draggable.set(false)),
+        ];
+
+        on_cleanup(move || {
+            for listener in listeners {
+                listener.remove();
+            }
+        });
+    });
+
+    on_cleanup(move || handle.set(None));
+
+    handle_ref
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct HoveredPanel {
-    id: Oco<'static, str>,
-    position: HoverPosition,
+/// Returns `true` if the panel has no drag handle, or the event was targeted inside of it.
+fn is_on_handle(
+    ev: &web_sys::Event,
+    handle: RwSignal<Option<web_sys::Element>, LocalStorage>,
+) -> bool {
+    let handle = handle.read_untracked();
+    let Some(handle) = handle.as_ref() else {
+        return true;
+    };
+    let target = ev
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+    handle.contains(target.as_ref())
 }
 
-pub fn provide_drag_reorder<const COLUMNS: usize, E>(
-    panel_order: [RwSignal<Vec<Oco<'static, str>>>; COLUMNS],
-) -> [NodeRef<E>; COLUMNS]
+/// Attribute added to panel elements, used to tell nested panels apart.
+const PANEL_ATTRIBUTE: &str = "data-drag-reorder-panel";
+
+/// Returns `true` if an event was targeted at this panel, and not a panel nested inside of it.
+fn is_innermost_panel<E>(ev: &web_sys::Event, node_ref: NodeRef<E>) -> bool
 where
     E: ElementType + 'static,
     E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
 {
-    let column_refs: Vec<NodeRef<E>> = panel_order
-        .iter()
-        .map(|_| NodeRef::new())
-        .collect::<Vec<_>>();
-    let ctx = DragReorderContext {
-        panel_order: panel_order.to_vec(),
-        column_refs: column_refs
-            .clone()
-            .into_iter()
-            .map(|column_ref| {
-                Signal::derive(move || {
-                    column_ref
-                        .get()
-                        .map(|column_ref| SendWrapper::new(column_ref.into()))
-                })
-            })
-            .collect(),
-        currently_dragged_panel: RwSignal::new(None),
-        hover_info: RwSignal::new(None),
-        panels: RwSignal::new(HashMap::new()),
+    let Some(panel) = node_ref.get_untracked() else {
+        return false;
     };
+    let panel: web_sys::Element = panel.into();
+    ev.target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .and_then(|target| {
+            target
+                .closest(&format!("[{PANEL_ATTRIBUTE}]"))
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|closest| closest == panel)
+}
 
-    Effect::new({
-        move |mut last_on_dragend: Option<Function>| {
-            if let Some(last_on_dragend) = last_on_dragend.take() {
-                let _ = document().remove_event_listener_with_callback("dragend", &last_on_dragend);
-            }
+/// Minimum distance in pixels the pointer must move before a pointer drag starts.
+const POINTER_DRAG_THRESHOLD: f64 = 4.0;
 
-            let on_dragend: Function = Closure::wrap(Box::new(move |_ev: web_sys::MouseEvent| {
-                if let Some((currently_dragged_panel, hover_info)) = ctx
-                    .currently_dragged_panel
-                    .read_untracked()
-                    .as_ref()
-                    .zip(ctx.hover_info.get_untracked())
-                {
-                    reorder_panel_order(&panel_order, &currently_dragged_panel, hover_info);
-                }
-            }) as Box<dyn FnMut(_)>)
-            .into_js_value()
-            .dyn_into()
-            .unwrap();
+/// Distance in pixels outside of a column's element at which panels are still dropped into it.
+const COLUMN_DROP_MARGIN: f64 = 24.0;
 
-            document()
-                .add_event_listener_with_callback("dragend", &on_dragend)
-                .unwrap();
+/// Returns the offset from the center of an element to the given mouse position.
+fn center_offset(el: &web_sys::Element, mouse_x: f64, mouse_y: f64) -> (f64, f64) {
+    let rect = el.get_bounding_client_rect();
 
-            on_cleanup({
-                let on_dragend = SendWrapper::new(on_dragend.clone());
-                move || {
-                    let _ = document()
-                        .remove_event_listener_with_callback("dragend", &on_dragend.take());
-                }
-            });
+    // Calculate the center of the element
+    let center_x = rect.x() + rect.width() / 2.0;
+    let center_y = rect.y() + rect.height() / 2.0;
 
-            on_dragend
-        }
-    });
+    // Calculate the offset from the mouse position to the center of the element
+    (mouse_x - center_x, mouse_y - center_y)
+}
 
-    provide_context(ctx);
-
-    column_refs
-        .try_into()
-        .ok()
-        .expect("vec should be same size as array")
-}
-
-fn reorder_panel_order(
-    panel_order: &[RwSignal<Vec<Oco<'static, str>>>],
-    currently_dragged_panel: &str,
-    hover_info: HoverInfo,
-) {
-    // Extract hover information
-    let HoverInfo {
-        column_index: to_col_index,
-        panel: maybe_hovered_panel,
-    } = hover_info;
-
-    // Initialize variables to store the original position of the dragged panel
-    let mut from_col_index = None;
-    let mut from_row_index = None;
-
-    // Find the column and row index of the currently dragged panel
-    for (col_idx, col_signal) in panel_order.iter().enumerate() {
-        let col_panels = col_signal.get_untracked();
-        if let Some(row_idx) = col_panels
-            .iter()
-            .position(|panel_id| panel_id.as_str() == currently_dragged_panel)
-        {
-            from_col_index = Some(col_idx);
-            from_row_index = Some(row_idx);
-            break;
+/// The browser APIs used to drag panels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DragBackend {
+    /// The HTML5 drag and drop API (`dragstart`, `dragover`, `dragend`).
+    ///
+    /// The browser renders a ghost image of the dragged panel, but most mobile browsers don't
+    /// fire these events for touch input.
+    #[default]
+    Html5,
+    /// Pointer events (`pointerdown`, `pointermove`, `pointerup`), which work for mouse, pen and touch.
+    ///
+    /// Assign [`UseDragReorderReturn::on_pointerdown`] to the panel, and give it `touch-action: none`
+    /// so touch drags don't scroll the page. No ghost image is rendered, so use
+    /// [`UseDragReorderReturn::is_dragging`] to style the panel being dragged.
+    Pointer,
+}
+
+/// Group membership of a provider, allowing panels to be transferred between separate providers.
+///
+/// Dropping a panel into another provider's column removes it from this provider's panel order and inserts it
+/// into the other's, along with the rest of the selection if it's selected. Providers which already have a
+/// panel with the same ID as a dragged panel won't accept it.
+#[derive(Clone, Debug)]
+pub struct DragGroup {
+    name: Oco<'static, str>,
+    shared: bool,
+    accept: Vec<Oco<'static, str>>,
+}
+
+impl DragGroup {
+    /// Creates a group which shares panels with, and accepts panels from, providers of the same name.
+    pub fn new(name: impl Into<Oco<'static, str>>) -> Self {
+        let name = name.into();
+        DragGroup {
+            accept: vec![name.clone()],
+            name,
+            shared: true,
         }
     }
 
-    // Proceed only if the dragged panel was found
-    if let (Some(from_col_index), Some(from_row_index)) = (from_col_index, from_row_index) {
-        // Get the target column's panels
-        let to_col_signal = &panel_order[to_col_index];
-        let mut to_col_panels = to_col_signal.get_untracked();
+    /// Sets whether panels can be dragged out of this provider into other providers. Defaults to `true`.
+    pub fn shared(mut self, shared: bool) -> Self {
+        self.shared = shared;
+        self
+    }
 
-        // Determine the insertion index
-        let insert_row_index = match maybe_hovered_panel {
-            Some(HoveredPanel {
-                id: hovered_panel_id,
-                position: hover_position,
-            }) => {
-                // Find the index of the hovered panel in the target column
-                if let Some(hovered_row_index) = to_col_panels
-                    .iter()
-                    .position(|panel_id| panel_id.as_str() == hovered_panel_id)
-                {
-                    // Determine the insertion index based on the hover position
-                    let mut idx = match hover_position {
-                        HoverPosition::Above => hovered_row_index,
-                        HoverPosition::Below => hovered_row_index + 1,
-                    };
+    /// Sets the names of the groups whose panels can be dropped into this provider. Defaults to this group.
+    pub fn accept<I>(mut self, groups: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Oco<'static, str>>,
+    {
+        self.accept = groups.into_iter().map(Into::into).collect();
+        self
+    }
+}
 
-                    // Adjust the insertion index if moving within the same column
-                    if from_col_index == to_col_index && from_row_index < idx {
-                        idx -= 1;
-                    }
-                    idx
-                } else {
-                    // If hovered panel is not found, insert at the end
-                    to_col_panels.len()
-                }
-            }
-            None => {
-                // No hovered panel; insert at the end of the column
-                to_col_panels.len()
-            }
-        };
+/// Options for [`use_drag_reorder_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct UseDragReorderOptions {
+    scope: Option<Oco<'static, str>>,
+    drag_image: Option<DragImage>,
+    drag_image_offset: Option<(i32, i32)>,
+}
 
-        // Remove the dragged panel from its original position
-        let from_col_signal = &panel_order[from_col_index];
-        let mut from_col_panels = from_col_signal.get_untracked();
-        from_col_panels.remove(from_row_index);
+impl UseDragReorderOptions {
+    /// Registers the panel with the provider of a [scope](DragReorderOptions::scope), instead of the
+    /// nearest provider.
+    pub fn scope(mut self, scope: impl Into<Oco<'static, str>>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
 
-        if from_col_index == to_col_index {
-            // Insert the panel into the same column at the new position
-            from_col_panels.insert(
-                insert_row_index,
-                Oco::from(currently_dragged_panel.to_string()),
-            );
-            from_col_signal.set(from_col_panels);
-        } else {
-            // Write back the modified original column
-            from_col_signal.set(from_col_panels);
+    /// Uses an element as the drag image instead of the browser's snapshot of the panel.
+    ///
+    /// The element must be part of the document when the drag starts, but can be positioned out of view.
+    pub fn drag_image_ref<E>(mut self, node_ref: NodeRef<E>) -> Self
+    where
+        E: ElementType + 'static,
+        E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    {
+        self.drag_image = Some(DragImage::Element(Arc::new(move || {
+            node_ref.get_untracked().map(Into::into)
+        })));
+        self
+    }
 
-            // Insert the panel into the new column
-            to_col_panels.insert(
-                insert_row_index,
-                Oco::from(currently_dragged_panel.to_string()),
-            );
-            to_col_signal.set(to_col_panels);
+    /// Renders a view to use as the drag image instead of the browser's snapshot of the panel.
+    ///
+    /// The view is rendered outside of the viewport when the drag starts, and removed once the browser has
+    /// taken its snapshot.
+    pub fn drag_image<V>(mut self, render: impl Fn() -> V + Send + Sync + 'static) -> Self
+    where
+        V: IntoView + 'static,
+    {
+        self.drag_image = Some(DragImage::Render(Arc::new(move || render().into_any())));
+        self
+    }
+
+    /// Sets the position of the cursor within the drag image, in pixels from its top left corner.
+    ///
+    /// Also applies to the browser's snapshot of the panel when no drag image is set.
+    pub fn drag_image_offset(mut self, x: i32, y: i32) -> Self {
+        self.drag_image_offset = Some((x, y));
+        self
+    }
+
+    /// Hides the drag image, for when a custom overlay follows the cursor instead.
+    pub fn hide_drag_image(mut self) -> Self {
+        self.drag_image = Some(DragImage::Hidden);
+        self
+    }
+}
+
+/// Options for [`provide_drag_reorder_with_options`].
+#[derive(Clone, Debug)]
+pub struct DragReorderOptions<K: 'static> {
+    scope: Option<Oco<'static, str>>,
+    group: Option<DragGroup>,
+    backend: DragBackend,
+    layout: Layout,
+    column_layouts: HashMap<usize, Layout>,
+    announcement_formatter: Option<Callback<Announcement<K>, String>>,
+    on_reorder: Option<Callback<ReorderEvent<K>>>,
+    on_cancel: Option<Callback<K>>,
+    on_remove: Option<Callback<RemoveEvent<K>>>,
+    can_drop: Option<Callback<(K, usize, usize), bool>>,
+    history_depth: usize,
+    animation: Option<ReorderAnimation>,
+    live_reorder: bool,
+    auto_scroll: AutoScroll,
+    #[cfg(feature = "persist")]
+    persist: Option<Persistence<K>>,
+}
+
+impl<K> Default for DragReorderOptions<K> {
+    fn default() -> Self {
+        DragReorderOptions {
+            scope: None,
+            group: None,
+            backend: DragBackend::default(),
+            layout: Layout::default(),
+            column_layouts: HashMap::new(),
+            announcement_formatter: None,
+            on_reorder: None,
+            on_cancel: None,
+            on_remove: None,
+            can_drop: None,
+            history_depth: DEFAULT_HISTORY_DEPTH,
+            animation: None,
+            live_reorder: false,
+            auto_scroll: AutoScroll::default(),
+            #[cfg(feature = "persist")]
+            persist: None,
         }
     }
 }
+
+impl<K> DragReorderOptions<K> {
+    /// Names the provider, so panels can target it with [`UseDragReorderOptions::scope`].
+    ///
+    /// Hooks use the nearest provider by default, so scopes are only needed when sibling providers share
+    /// a component, or to reach an outer provider from inside a nested one.
+    pub fn scope(mut self, scope: impl Into<Oco<'static, str>>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Adds the provider to a [`DragGroup`], allowing panels to be dragged to and from other providers.
+    pub fn group(mut self, group: DragGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Sets the backend used to drag panels. Defaults to [`DragBackend::Html5`].
+    pub fn backend(mut self, backend: DragBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Sets the layout of all columns. Defaults to [`Layout::Vertical`].
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the layout of a single column, overriding [`DragReorderOptions::layout`].
+    pub fn column_layout(mut self, column_index: usize, layout: Layout) -> Self {
+        self.column_layouts.insert(column_index, layout);
+        self
+    }
+
+    /// Sets the function which formats [`Announcement`]s for screen readers, for example to localize them.
+    ///
+    /// Returning an empty string skips the announcement. Defaults to the [`Display`](std::fmt::Display)
+    /// implementation of [`Announcement`].
+    pub fn announcement_formatter(
+        mut self,
+        formatter: impl Fn(Announcement<K>) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.announcement_formatter = Some(Callback::new(formatter));
+        self
+    }
+
+    /// Sets a callback which is called with a [`ReorderEvent`] each time a panel is dropped into a new
+    /// position.
+    ///
+    /// Moves made, undone or redone with the [`DragReorderHandle`] are reported too.
+    /// Panels dropped back into their original position aren't reported. Panels transferred from another
+    /// provider of a [`DragGroup`] are reported once, to the provider they're dropped into, with
+    /// [`ReorderEvent::transfer`] describing where they came from.
+    pub fn on_reorder(
+        mut self,
+        on_reorder: impl Fn(ReorderEvent<K>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_reorder = Some(Callback::new(on_reorder));
+        self
+    }
+
+    /// Sets a callback which is called with the dragged panel's ID when a drag is cancelled, leaving
+    /// the panel order unchanged.
+    ///
+    /// Drags are cancelled by pressing Escape, by dropping outside of every column or where
+    /// [`can_drop`](Self::can_drop) disallows it, or when the browser interrupts the drag.
+    pub fn on_cancel(mut self, on_cancel: impl Fn(K) + Send + Sync + 'static) -> Self {
+        self.on_cancel = Some(Callback::new(on_cancel));
+        self
+    }
+
+    /// Sets a callback which is called with a [`RemoveEvent`] each time a panel is removed with
+    /// [`DragReorderHandle::remove`], or put back by undoing the removal.
+    pub fn on_remove(mut self, on_remove: impl Fn(RemoveEvent<K>) + Send + Sync + 'static) -> Self {
+        self.on_remove = Some(Callback::new(on_remove));
+        self
+    }
+
+    /// Sets a predicate which decides whether a panel can be dropped at a position, for example to keep
+    /// a column read-only or to stop a panel leaving its column.
+    ///
+    /// The predicate is called with the dragged panel's ID, the target column, and the index the panel
+    /// would have in that column once dropped. When a selection is dragged, it's called for each selected
+    /// panel, and the drop is only allowed if every panel is. Disallowed positions show no hover indicator,
+    /// and dropping there cancels the drag. Providers in a [`DragGroup`] consult their own predicate for incoming panels.
+    pub fn can_drop(
+        mut self,
+        can_drop: impl Fn(&K, usize, usize) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.can_drop = Some(Callback::new(
+            move |(id, column_index, index): (K, usize, usize)| can_drop(&id, column_index, index),
+        ));
+        self
+    }
+
+    /// Sets the number of moves which can be undone with [`DragReorderHandle::undo`]. Defaults to 100.
+    ///
+    /// Setting the depth to `0` disables the history. The history is cleared whenever the panel order is
+    /// changed outside of the provider, such as by adding a panel to a column's signal, since its moves may
+    /// no longer apply. Panels removed with [`DragReorderHandle::remove`] are recorded instead.
+    pub fn history_depth(mut self, depth: usize) -> Self {
+        self.history_depth = depth;
+        self
+    }
+
+    /// Animates panels moving to their new position when the panel order changes from a drop, undo or redo.
+    pub fn animation(mut self, animation: ReorderAnimation) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+
+    /// Moves panels out of the way of the dragged panel while dragging, instead of only reporting a
+    /// [`HoverPosition`].
+    ///
+    /// The provisional layout is available from [`DragReorderHandle::preview_order`], which should be rendered
+    /// in place of the panel order. It's committed to the panel order on drop, and reverted when the drag is
+    /// cancelled. Panels dragged into another provider of a [`DragGroup`] aren't previewed.
+    pub fn live_reorder(mut self, live_reorder: bool) -> Self {
+        self.live_reorder = live_reorder;
+        self
+    }
+
+    /// Sets how the window and scroll containers of columns scroll when a panel is dragged near their edges.
+    ///
+    /// Auto scrolling is enabled by default, and can be disabled with a max speed of `0`.
+    pub fn auto_scroll(mut self, auto_scroll: AutoScroll) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+
+    /// Persists the panel order to browser storage, restoring it when the provider is created.
+    #[cfg(feature = "persist")]
+    pub fn persist(mut self, persist: Persist) -> Self
+    where
+        K: PanelId + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.persist = Some(Persistence::new(persist));
+        self
+    }
+}
+
+/// The default number of moves which can be undone.
+const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// Whether a panel of a provider is being dragged, returned from [`use_drag_state`].
+///
+/// Positions are `(column_index, index)` pairs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DragState<K> {
+    /// No panel is being dragged.
+    Idle,
+    /// A panel is being dragged, with the mouse, pointer or keyboard.
+    Dragging {
+        /// The ID of the dragged panel.
+        id: K,
+        /// The current position of the dragged panel.
+        source: (usize, usize),
+        /// The position the dragged panel would be dropped at, not counting the dragged panels, or `None`
+        /// if dropping now would cancel the drag or move the panel to another provider.
+        target: Option<(usize, usize)>,
+    },
+}
+
+impl<K> DragState<K> {
+    /// Returns `true` if a panel is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        matches!(self, DragState::Dragging { .. })
+    }
+}
+
+/// A completed move of a panel, passed to [`DragReorderOptions::on_reorder`].
+///
+/// Column and panel indexes are zero based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReorderEvent<K> {
+    /// The ID of the moved panel.
+    pub id: K,
+    /// The IDs of every moved panel, including `id`.
+    ///
+    /// Only contains `id`, unless several [selected](UseDragReorderReturn::is_selected) panels were
+    /// dragged together.
+    pub ids: Vec<K>,
+    /// The column the panel `id` was moved from.
+    pub from_column: usize,
+    /// The position of the panel within its original column.
+    pub from_index: usize,
+    /// The column the panel was moved to.
+    pub to_column: usize,
+    /// The position of the panel within its new column.
+    pub to_index: usize,
+    /// The panel order of every column before the move.
+    pub before: Vec<Vec<K>>,
+    /// The panel order of every column after the move.
+    pub after: Vec<Vec<K>>,
+    /// The providers the panels were transferred between, if they were dropped into another provider of a
+    /// [`DragGroup`].
+    ///
+    /// For transfers, `from_column` and `from_index` refer to the provider the panels were dragged out of,
+    /// while the other indexes and the `before` and `after` panel orders refer to the provider they were
+    /// dropped into.
+    pub transfer: Option<Transfer<K>>,
+}
+
+/// The providers of a [`ReorderEvent`] which transferred panels between providers of a [`DragGroup`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transfer<K> {
+    /// The group of the provider the panels were dragged out of.
+    pub from_group: Oco<'static, str>,
+    /// The [scope](DragReorderOptions::scope) of the provider the panels were dragged out of, if it has one.
+    pub from_scope: Option<Oco<'static, str>>,
+    /// The group of the provider the panels were dropped into.
+    pub to_group: Oco<'static, str>,
+    /// The [scope](DragReorderOptions::scope) of the provider the panels were dropped into, if it has one.
+    pub to_scope: Option<Oco<'static, str>>,
+    /// The panel order of every column of the provider the panels were dragged out of, before the move.
+    pub from_before: Vec<Vec<K>>,
+    /// The panel order of every column of the provider the panels were dragged out of, after the move.
+    pub from_after: Vec<Vec<K>>,
+}
+
+/// A panel removed with [`DragReorderHandle::remove`], passed to [`DragReorderOptions::on_remove`].
+///
+/// Column and panel indexes are zero based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoveEvent<K> {
+    /// The ID of the removed panel.
+    pub id: K,
+    /// The column the panel was removed from.
+    pub column: usize,
+    /// The position of the panel within its column.
+    pub index: usize,
+    /// `true` if the panel was put back at its position by undoing the removal.
+    pub restored: bool,
+    /// The panel order of every column before the change.
+    pub before: Vec<Vec<K>>,
+    /// The panel order of every column after the change.
+    pub after: Vec<Vec<K>>,
+}
+
+/// The elements of each column, keyed by the column's panel IDs.
+pub(crate) type ColumnRefs<K> = RwSignal<HashMap<RwSignal<Vec<K>>, SendWrapper<web_sys::Element>>>;
+
+#[derive(Clone)]
+struct DragReorderContext<K: PanelId> {
+    column_refs: ColumnRefs<K>,
+    panel_order: Signal<Vec<RwSignal<Vec<K>>>>,
+    currently_dragged_panel: RwSignal<Option<K>>,
+    hover_info: RwSignal<Option<HoverInfo<K>>>,
+    /// Panel being dragged in from another provider of the same group.
+    incoming_panel: RwSignal<Option<K>>,
+    panels: PanelElements<K>,
+    keyboard_dragging: RwSignal<bool>,
+    /// Offset from the pointer to the center of the panel dragged with [`DragBackend::Html5`].
+    drag_offset: StoredValue<(f64, f64)>,
+    group: Option<DragGroup>,
+    scope: Option<Oco<'static, str>>,
+    backend: DragBackend,
+    layout: Layout,
+    column_layouts: HashMap<usize, Layout>,
+    announcer: Announcer<K>,
+    on_reorder: Option<Callback<ReorderEvent<K>>>,
+    on_cancel: Option<Callback<K>>,
+    on_remove: Option<Callback<RemoveEvent<K>>>,
+    can_drop: Option<Callback<(K, usize, usize), bool>>,
+    history: RwSignal<History<K>>,
+    /// The panel order as of the last move or undo, to detect changes made outside of the provider.
+    known_panel_order: StoredValue<Vec<Vec<K>>>,
+    animation: Option<ReorderAnimation>,
+    live_reorder: bool,
+    /// Provisional panel order of every column while dragging with [`DragReorderOptions::live_reorder`].
+    preview_order: RwSignal<Option<Vec<Vec<K>>>>,
+    auto_scroller: AutoScroller,
+    /// Selected panels, which are dragged together.
+    selection: RwSignal<Vec<K>>,
+    /// The panel which shift-click selects a range from.
+    selection_anchor: RwSignal<Option<K>>,
+}
+
+impl<K: PanelId> DragReorderContext<K> {
+    /// Updates the hover info from the pointer's position, and the offset from it to the dragged panel's
+    /// center.
+    ///
+    /// The column is chosen by the pointer, while the hovered panel is chosen by the dragged panel's center.
+    /// The closest column may belong to another provider in the same [`DragGroup`], in which case
+    /// the hover info is set on that provider instead. Outside of every column, the hover info is cleared.
+    ///
+    /// Returns `false` if the dragged panel can't be dropped at the pointer's position.
+    fn update_hover_info(
+        &self,
+        mouse_x: f64,
+        mouse_y: f64,
+        (offset_x, offset_y): (f64, f64),
+    ) -> bool {
+        let Some(dragged_id) = self.currently_dragged_panel.get_untracked() else {
+            return false;
+        };
+
+        let moved = self.moved_panels(&dragged_id);
+        let mut targets = vec![self.clone()];
+        targets.extend(self.transfer_targets(&moved));
+        let closest_column = targets
+            .iter()
+            .filter_map(|target| {
+                target
+                    .closest_column(mouse_x, mouse_y)
+                    .map(|(column_index, dist)| (target, column_index, dist))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+        let mut can_drop = false;
+        for target in &targets {
+            let new_hover_info = closest_column
+                .filter(|(closest_target, _, _)| target.is_same_provider(closest_target))
+                .map(|(_, column_index, _)| {
+                    target.hover_info_in_column(
+                        column_index,
+                        mouse_x - offset_x,
+                        mouse_y - offset_y,
+                    )
+                })
+                .filter(|hover_info| target.can_drop_at(&moved, hover_info));
+            let is_hovered = new_hover_info.is_some();
+            can_drop |= is_hovered;
+            target.hover_info.maybe_update(move |hovered| {
+                if hovered != &new_hover_info {
+                    *hovered = new_hover_info;
+                    true
+                } else {
+                    false
+                }
+            });
+
+            if !target.is_same_provider(self) {
+                let incoming_panel = is_hovered.then(|| dragged_id.clone());
+                target.incoming_panel.maybe_update(move |incoming| {
+                    if incoming != &incoming_panel {
+                        *incoming = incoming_panel;
+                        true
+                    } else {
+                        false
+                    }
+                });
+            }
+        }
+
+        can_drop
+    }
+
+    /// Selects or deselects a panel, making it the anchor of range selections.
+    fn set_selected(&self, id: &K, selected: bool) {
+        self.selection.maybe_update(|selection| {
+            let index = selection.iter().position(|selected| selected == id);
+            match (index, selected) {
+                (None, true) => selection.push(id.clone()),
+                (Some(index), false) => {
+                    selection.remove(index);
+                }
+                _ => return false,
+            }
+            true
+        });
+        self.selection_anchor.set(Some(id.clone()));
+    }
+
+    /// Selects every panel between the anchor and a panel, in the order panels appear across columns.
+    fn select_range(&self, id: &K) {
+        let Some(anchor) = self.selection_anchor.get_untracked() else {
+            self.set_selected(id, true);
+            return;
+        };
+        let panels = self
+            .panel_order
+            .read_untracked()
+            .iter()
+            .flat_map(|column| column.get_untracked())
+            .collect::<Vec<_>>();
+        let (Some(start), Some(end)) = (
+            panels.iter().position(|panel_id| *panel_id == anchor),
+            panels.iter().position(|panel_id| panel_id == id),
+        ) else {
+            self.set_selected(id, true);
+            return;
+        };
+
+        let range = &panels[start.min(end)..=start.max(end)];
+        self.selection.update(|selection| {
+            for panel_id in range {
+                if !selection.contains(panel_id) {
+                    selection.push(panel_id.clone());
+                }
+            }
+        });
+    }
+
+    /// Records a move in the history, and notifies the `on_reorder` callback.
+    fn commit_move(&self, event: ReorderEvent<K>) {
+        let moves = event
+            .ids
+            .iter()
+            .filter_map(|id| {
+                Some(PanelMove {
+                    id: id.clone(),
+                    from: position_in(&event.before, id)?,
+                    to: position_in(&event.after, id)?,
+                })
+            })
+            .collect();
+        self.history
+            .update(|history| history.record(HistoryEntry::Moved(moves)));
+        self.known_panel_order.set_value(event.after.clone());
+        if let Some(on_reorder) = self.on_reorder {
+            on_reorder.run(event);
+        }
+    }
+
+    fn undo(&self) {
+        if self.currently_dragged_panel.read_untracked().is_some() {
+            return;
+        }
+        if let Some(entry) = self.history.try_update(|history| history.undo()).flatten() {
+            self.apply_history_entry(entry.reversed());
+        }
+    }
+
+    fn redo(&self) {
+        if self.currently_dragged_panel.read_untracked().is_some() {
+            return;
+        }
+        if let Some(entry) = self.history.try_update(|history| history.redo()).flatten() {
+            self.apply_history_entry(entry);
+        }
+    }
+
+    /// Applies a history entry, notifying the `on_reorder` or `on_remove` callback.
+    fn apply_history_entry(&self, entry: HistoryEntry<K>) {
+        match entry {
+            HistoryEntry::Moved(moves) => {
+                let Some(id) = moves.first().map(|panel_move| panel_move.id.clone()) else {
+                    return;
+                };
+                let placements = moves
+                    .into_iter()
+                    .map(|PanelMove { id, to, .. }| (id, to))
+                    .collect::<Vec<_>>();
+                animate_reorder(self.animation.as_ref(), &[self.panels], || {
+                    let panel_order = self.panel_order.read_untracked();
+                    let before = snapshot_panel_order(&panel_order);
+                    let event = place_panels(&panel_order, &id, before, &placements);
+                    self.known_panel_order
+                        .set_value(snapshot_panel_order(&panel_order));
+                    if let (Some(on_reorder), Some(event)) = (self.on_reorder, event) {
+                        on_reorder.run(event);
+                    }
+                });
+            }
+            HistoryEntry::Removed { id, .. } => {
+                self.remove_panel_unrecorded(&id);
+            }
+            HistoryEntry::Restored {
+                id,
+                position: (column_index, index),
+            } => {
+                if self.position_of(&id).is_some() {
+                    return;
+                }
+                let Some(column) = self.column(column_index) else {
+                    return;
+                };
+                let before = snapshot_panel_order(&self.panel_order.read_untracked());
+                animate_reorder(self.animation.as_ref(), &[self.panels], || {
+                    column.update(|panels| panels.insert(index.min(panels.len()), id.clone()));
+                });
+                self.notify_removal(id, before, true);
+            }
+        }
+    }
+
+    /// Moves a panel to an index in a column, not counting the panel itself, as if it had been dropped there.
+    ///
+    /// Returns `true` if the panel was moved.
+    fn move_panel(&self, id: &K, column_index: usize, index: usize) -> bool {
+        if self.currently_dragged_panel.read_untracked().is_some()
+            || self.column(column_index).is_none()
+            || self.position_of(id).is_none()
+        {
+            return false;
+        }
+        let index = index.min(self.other_panels(column_index, id).len());
+        if !self.can_drop(id, column_index, index) {
+            return false;
+        }
+
+        let mut moved = false;
+        animate_reorder(self.animation.as_ref(), &[self.panels], || {
+            let panel_order = self.panel_order.read_untracked();
+            let before = snapshot_panel_order(&panel_order);
+            let placements = [(id.clone(), (column_index, index))];
+            if let Some(event) = place_panels(&panel_order, id, before, &placements) {
+                moved = true;
+                self.commit_move(event);
+            }
+        });
+        moved
+    }
+
+    /// Removes a panel from its column, recording the removal in the history.
+    ///
+    /// Returns `true` if the panel was removed.
+    fn remove_panel(&self, id: &K) -> bool {
+        if self.currently_dragged_panel.read_untracked().is_some() {
+            return false;
+        }
+        let Some(position) = self.remove_panel_unrecorded(id) else {
+            return false;
+        };
+        self.history.update(|history| {
+            history.record(HistoryEntry::Removed {
+                id: id.clone(),
+                position,
+            })
+        });
+        true
+    }
+
+    /// Removes a panel from its column and notifies the `on_remove` callback, returning the position it
+    /// was removed from.
+    fn remove_panel_unrecorded(&self, id: &K) -> Option<(usize, usize)> {
+        let (column_index, index) = self.position_of(id)?;
+        let column = self.column(column_index)?;
+
+        let before = snapshot_panel_order(&self.panel_order.read_untracked());
+        animate_reorder(self.animation.as_ref(), &[self.panels], || {
+            column.update(|panels| {
+                panels.remove(index);
+            });
+        });
+        self.notify_removal(id.clone(), before, false);
+        Some((column_index, index))
+    }
+
+    /// Notes the panel order after a panel was removed or restored, and notifies the `on_remove` callback.
+    fn notify_removal(&self, id: K, before: Vec<Vec<K>>, restored: bool) {
+        let after = snapshot_panel_order(&self.panel_order.read_untracked());
+        self.known_panel_order.set_value(after.clone());
+        let Some(on_remove) = self.on_remove else {
+            return;
+        };
+        let Some((column, index)) = position_in(if restored { &after } else { &before }, &id)
+        else {
+            return;
+        };
+        on_remove.run(RemoveEvent {
+            id,
+            column,
+            index,
+            restored,
+            before,
+            after,
+        });
+    }
+
+    /// Returns the panels moved by dragging a panel of this provider: the whole selection if the panel is
+    /// selected, or otherwise just the panel.
+    fn moved_panels(&self, dragged_id: &K) -> Vec<K> {
+        engine::moved_panels(
+            &snapshot_panel_order(&self.panel_order.read_untracked()),
+            dragged_id,
+            &self.selection.read_untracked(),
+        )
+    }
+
+    /// Returns `true` if every moved panel is allowed to be dropped at its new position in this provider.
+    ///
+    /// Dropping a selected panel moves the whole selection, so each selected panel is checked at the
+    /// position it would land at.
+    fn can_drop_at(&self, moved: &[K], hover_info: &HoverInfo<K>) -> bool {
+        let placements = engine::insert_placements(
+            &snapshot_panel_order(&self.panel_order.read_untracked()),
+            moved.to_vec(),
+            hover_info,
+        );
+        !placements.is_empty()
+            && engine::placements_allowed(&placements, |id, column_index, index| {
+                self.can_drop(id, column_index, index)
+            })
+    }
+
+    /// Returns `true` if a panel is allowed to be dropped at a position.
+    fn can_drop(&self, dragged_id: &K, column_index: usize, index: usize) -> bool {
+        self.can_drop
+            .is_none_or(|can_drop| can_drop.run((dragged_id.clone(), column_index, index)))
+    }
+
+    /// Returns the index and distance of the column closest to the pointer, among the columns which are
+    /// within [`COLUMN_DROP_MARGIN`] of it.
+    fn closest_column(&self, mouse_x: f64, mouse_y: f64) -> Option<(usize, f64)> {
+        let column_refs = self.column_refs.read_untracked();
+        let (closest_column, closest_dist) =
+            self.panel_order.read_untracked().iter().enumerate().fold(
+                (None, f64::INFINITY),
+                |(column, closest_dist), (i, column_panels)| {
+                    let Some(column_ref) = column_refs.get(column_panels) else {
+                        return (column, closest_dist);
+                    };
+                    // Panels dropped outside of every column are cancelled
+                    let rect = column_ref.get_bounding_client_rect();
+                    let is_near = mouse_x >= rect.left() - COLUMN_DROP_MARGIN
+                        && mouse_x <= rect.right() + COLUMN_DROP_MARGIN
+                        && mouse_y >= rect.top() - COLUMN_DROP_MARGIN
+                        && mouse_y <= rect.bottom() + COLUMN_DROP_MARGIN;
+                    if !is_near {
+                        return (column, closest_dist);
+                    }
+
+                    // Columns are compared across their layout axis, or by distance to the edges of grids
+                    let dist = match self.layout(i) {
+                        Layout::Vertical => (mouse_x - (rect.left() + rect.width() / 2.0)).abs(),
+                        Layout::Horizontal => (mouse_y - (rect.top() + rect.height() / 2.0)).abs(),
+                        Layout::Grid => {
+                            let dist_x =
+                                (rect.left() - mouse_x).max(mouse_x - rect.right()).max(0.0);
+                            let dist_y =
+                                (rect.top() - mouse_y).max(mouse_y - rect.bottom()).max(0.0);
+                            dist_x.hypot(dist_y)
+                        }
+                    };
+                    if dist < closest_dist {
+                        (Some(i), dist)
+                    } else {
+                        (column, closest_dist)
+                    }
+                },
+            );
+
+        closest_column.map(|column_index| (column_index, closest_dist))
+    }
+
+    /// Returns the hover info for a position within a column.
+    fn hover_info_in_column(
+        &self,
+        column_index: usize,
+        mouse_x: f64,
+        mouse_y: f64,
+    ) -> HoverInfo<K> {
+        // Moving panels follow the pointer in live reorders, so only the panels around them are compared
+        let moved = match self.currently_dragged_panel.get_untracked() {
+            Some(dragged_id) if self.live_reorder => engine::moved_panels(
+                &snapshot_panel_order(&self.panel_order.read_untracked()),
+                &dragged_id,
+                &self.selection.read_untracked(),
+            ),
+            _ => Vec::new(),
+        };
+
+        // Panels are compared along the column's layout axis
+        let layout = self.layout(column_index);
+        let (closest_panel, _) = self.panels.read_untracked().iter().fold(
+            (None, f64::INFINITY),
+            |(closest_panel, closest_dist), (panel_id, panel_ref)| {
+                let is_in_column = self
+                    .column(column_index)
+                    .map(|column_panels| column_panels.read_untracked().contains(panel_id))
+                    .unwrap_or(false);
+                if !is_in_column || moved.contains(panel_id) {
+                    return (closest_panel, closest_dist);
+                }
+
+                let rect = panel_ref.get_bounding_client_rect();
+                let center_x = rect.left() + rect.width() / 2.0;
+                let center_y = rect.top() + rect.height() / 2.0;
+                let dist = match layout {
+                    Layout::Vertical => (mouse_y - center_y).abs(),
+                    Layout::Horizontal => (mouse_x - center_x).abs(),
+                    Layout::Grid => (mouse_x - center_x).hypot(mouse_y - center_y),
+                };
+                if dist < closest_dist {
+                    (Some((panel_id.clone(), rect)), dist)
+                } else {
+                    (closest_panel, closest_dist)
+                }
+            },
+        );
+
+        let panel = closest_panel.map(|(panel_id, rect)| {
+            let center_x = rect.left() + rect.width() / 2.0;
+            let center_y = rect.top() + rect.height() / 2.0;
+            let is_before = match layout {
+                Layout::Vertical => mouse_y < center_y,
+                Layout::Horizontal => mouse_x < center_x,
+                // Reading order: rows above come first, then the left side of the same row
+                Layout::Grid => {
+                    mouse_y < rect.top() || (mouse_y <= rect.bottom() && mouse_x < center_x)
+                }
+            };
+            let (before, after) = layout.hover_positions();
+            HoveredPanel {
+                id: panel_id,
+                position: if is_before { before } else { after },
+            }
+        });
+
+        HoverInfo {
+            column_index,
+            panel,
+        }
+    }
+
+    /// Moves the currently dragged panel to the position it's hovering over, which may be in another provider.
+    ///
+    /// The drag is cancelled if the panel isn't hovering over a position it can be dropped into.
+    fn drop_dragged_panel(&self) {
+        let Some(currently_dragged_panel) = self.currently_dragged_panel.get_untracked() else {
+            return;
+        };
+        let moved = self.moved_panels(&currently_dragged_panel);
+        let can_drop = |target: &DragReorderContext<K>, hover_info: &HoverInfo<K>| {
+            target.can_drop_at(&moved, hover_info)
+        };
+
+        if let Some(hover_info) = self
+            .hover_info
+            .get_untracked()
+            .filter(|hover_info| can_drop(self, hover_info))
+        {
+            animate_reorder(self.animation.as_ref(), &[self.panels], || {
+                let event = reorder_panel_order(
+                    &self.panel_order.read_untracked(),
+                    &currently_dragged_panel,
+                    &self.selection.read_untracked(),
+                    hover_info,
+                );
+                if let Some(event) = event {
+                    self.commit_move(event);
+                }
+            });
+            self.announce_position(AnnouncementKind::Dropped, currently_dragged_panel);
+            return;
+        }
+
+        let hovered_target = self
+            .transfer_targets(&moved)
+            .into_iter()
+            .find_map(|target| {
+                let hover_info = target
+                    .hover_info
+                    .get_untracked()
+                    .filter(|hover_info| can_drop(&target, hover_info))?;
+                Some((target, hover_info))
+            });
+        match hovered_target {
+            Some((target, hover_info)) => {
+                let mut event = None;
+                animate_reorder(
+                    self.animation.as_ref(),
+                    &[self.panels, target.panels],
+                    || {
+                        event = self.transfer_panels(
+                            &target,
+                            &currently_dragged_panel,
+                            moved,
+                            &hover_info,
+                        );
+                    },
+                );
+                target.announce_position(AnnouncementKind::Dropped, currently_dragged_panel);
+                if let (Some(on_reorder), Some(event)) = (target.on_reorder, event) {
+                    on_reorder.run(event);
+                }
+            }
+            None => self.notify_cancelled(currently_dragged_panel),
+        }
+    }
+
+    /// Moves panels from this provider into another provider's column, keeping their order.
+    ///
+    /// Every column is written before any effects run, so the move is observed as a single change. Neither
+    /// provider records the transfer in its history, since undoing it would change the other provider's
+    /// panels, but both keep their history.
+    fn transfer_panels(
+        &self,
+        target: &DragReorderContext<K>,
+        id: &K,
+        moved: Vec<K>,
+        hover_info: &HoverInfo<K>,
+    ) -> Option<ReorderEvent<K>> {
+        let from_panel_order = self.panel_order.read_untracked();
+        let from_before = snapshot_panel_order(&from_panel_order);
+        let (from_column, from_index) = position_in(&from_before, id)?;
+        let to_panel_order = target.panel_order.read_untracked();
+        let before = snapshot_panel_order(&to_panel_order);
+        let placements = engine::insert_placements(&before, moved.clone(), hover_info);
+        let (to_column, to_index) = placements
+            .iter()
+            .find_map(|(panel_id, position)| (panel_id == id).then_some(*position))?;
+
+        let mut from_after = from_before.clone();
+        for column in &mut from_after {
+            column.retain(|panel_id| !moved.contains(panel_id));
+        }
+        let mut after = before.clone();
+        for (panel_id, (column_index, index)) in placements {
+            let column = &mut after[column_index];
+            column.insert(index.min(column.len()), panel_id);
+        }
+
+        for (panel_order, before, after) in [
+            (&from_panel_order, &from_before, &from_after),
+            (&to_panel_order, &before, &after),
+        ] {
+            for ((column, column_before), column_after) in panel_order.iter().zip(before).zip(after)
+            {
+                if column_before != column_after {
+                    column.set(column_after.clone());
+                }
+            }
+        }
+        self.known_panel_order.set_value(from_after.clone());
+        target.known_panel_order.set_value(after.clone());
+
+        Some(ReorderEvent {
+            id: id.clone(),
+            ids: moved,
+            from_column,
+            from_index,
+            to_column,
+            to_index,
+            before,
+            after,
+            transfer: Some(Transfer {
+                from_group: self.group_name(),
+                from_scope: self.scope.clone(),
+                to_group: target.group_name(),
+                to_scope: target.scope.clone(),
+                from_before,
+                from_after,
+            }),
+        })
+    }
+
+    /// Returns the name of this provider's [`DragGroup`].
+    fn group_name(&self) -> Oco<'static, str> {
+        self.group
+            .as_ref()
+            .map(|group| group.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the other providers which the moved panels can be dragged into.
+    ///
+    /// Providers which already have a panel with the same ID as one of the moved panels are skipped.
+    fn transfer_targets(&self, moved: &[K]) -> Vec<DragReorderContext<K>> {
+        let Some(group) = self.group.as_ref().filter(|group| group.shared) else {
+            return Vec::new();
+        };
+
+        drag_group_providers::<K>()
+            .into_iter()
+            .filter(|target| {
+                !target.is_same_provider(self)
+                    && target
+                        .group
+                        .as_ref()
+                        .is_some_and(|target_group| target_group.accept.contains(&group.name))
+                    && moved.iter().all(|id| target.position_of(id).is_none())
+            })
+            .collect()
+    }
+
+    /// Returns a signal of the drag state.
+    fn drag_state(&self) -> Signal<DragState<K>> {
+        let DragReorderContext {
+            panel_order,
+            currently_dragged_panel,
+            hover_info,
+            selection,
+            ..
+        } = self.clone();
+        Memo::new(move |_| {
+            let Some(id) = currently_dragged_panel.get() else {
+                return DragState::Idle;
+            };
+            let Some(source) = position_in(&tracked_panel_order(&panel_order.read()), &id) else {
+                return DragState::Idle;
+            };
+            let target = hover_info.read().as_ref().map(|hover_info| {
+                let (index, _) = tracked_insertion_index(panel_order, selection, &id, hover_info);
+                (hover_info.column_index, index)
+            });
+            DragState::Dragging { id, source, target }
+        })
+        .into()
+    }
+
+    /// Returns `true` if a panel of this provider is being dragged with the browser's drag and drop.
+    fn is_native_dragging(&self) -> bool {
+        self.currently_dragged_panel.read_untracked().is_some()
+            && !self.keyboard_dragging.get_untracked()
+    }
+
+    /// Returns `true` if both contexts were created by the same provider.
+    fn is_same_provider(&self, other: &DragReorderContext<K>) -> bool {
+        self.hover_info == other.hover_info
+    }
+
+    /// Picks up a panel with the keyboard, hovering over its current position.
+    fn start_keyboard_drag(&self, id: K) {
+        let Some((column_index, panel_index)) = self.position_of(&id) else {
+            return;
+        };
+
+        self.hover_info
+            .set(Some(self.hover_info_at(&id, column_index, panel_index)));
+        self.currently_dragged_panel.set(Some(id));
+        self.keyboard_dragging.set(true);
+    }
+
+    /// Moves the keyboard dragged panel's target in the direction of an arrow key.
+    ///
+    /// Arrows along the hovered column's layout move within the column, and other arrows move across columns.
+    /// In grids, up and down move by a row, and only move across columns from the first or last row.
+    fn move_keyboard_drag(&self, delta_x: isize, delta_y: isize) {
+        let Some(id) = self.currently_dragged_panel.get_untracked() else {
+            return;
+        };
+        let Some(hover_info) = self.hover_info.get_untracked() else {
+            return;
+        };
+
+        // Skip over positions which the panel can't be dropped into
+        let moved = self.moved_panels(&id);
+        let mut position = self.insertion_point(&id, &hover_info);
+        loop {
+            let next_position = self.keyboard_step(&id, position, delta_x, delta_y);
+            if next_position == position {
+                return;
+            }
+            position = next_position;
+
+            let (column_index, panel_index) = position;
+            let hover_info = self.hover_info_at(&id, column_index, panel_index);
+            if self.can_drop_at(&moved, &hover_info) {
+                self.hover_info.set(Some(hover_info));
+                return;
+            }
+        }
+    }
+
+    /// Returns the insertion point one keyboard step away from `position`.
+    fn keyboard_step(
+        &self,
+        id: &K,
+        (column_index, panel_index): (usize, usize),
+        delta_x: isize,
+        delta_y: isize,
+    ) -> (usize, usize) {
+        let (column_delta, row_delta) = match self.layout(column_index) {
+            Layout::Vertical => (delta_x, delta_y),
+            Layout::Horizontal => (delta_y, delta_x),
+            Layout::Grid => {
+                let row_len = self.grid_row_len(column_index) as isize;
+                let other_panels_len = self.other_panels(column_index, id).len() as isize;
+                let target_index = panel_index as isize + delta_y * row_len;
+                if delta_y != 0 && !(0..=other_panels_len).contains(&target_index) {
+                    (delta_y, 0)
+                } else {
+                    (0, delta_x + delta_y * row_len)
+                }
+            }
+        };
+        let column_index = column_index
+            .saturating_add_signed(column_delta)
+            .min(self.panel_order.read_untracked().len().saturating_sub(1));
+        let panel_index = panel_index
+            .saturating_add_signed(row_delta)
+            .min(self.other_panels(column_index, id).len());
+
+        (column_index, panel_index)
+    }
+
+    /// Clears the drag state once a panel has been dropped.
+    fn end_drag(&self) {
+        if let Some(id) = self.currently_dragged_panel.get_untracked() {
+            for target in drag_group_providers::<K>() {
+                if target.incoming_panel.read_untracked().as_ref() == Some(&id) {
+                    target.incoming_panel.set(None);
+                    target.hover_info.set(None);
+                }
+            }
+        }
+
+        self.auto_scroller.stop();
+        self.keyboard_dragging.set(false);
+        self.hover_info.set(None);
+        self.currently_dragged_panel.set(None);
+    }
+
+    /// Stops dragging without moving the panel.
+    fn cancel_drag(&self) {
+        if let Some(id) = self.currently_dragged_panel.get_untracked() {
+            self.notify_cancelled(id);
+        }
+        self.end_drag();
+    }
+
+    /// Announces a cancelled drag, and notifies the `on_cancel` callback.
+    fn notify_cancelled(&self, id: K) {
+        self.announce_position(AnnouncementKind::Cancelled, id.clone());
+        if let Some(on_cancel) = self.on_cancel {
+            on_cancel.run(id);
+        }
+    }
+
+    /// Announces the current position of a panel.
+    fn announce_position(&self, kind: AnnouncementKind, id: K) {
+        let Some((column_index, index)) = self.position_of(&id) else {
+            return;
+        };
+        let column_len = self.other_panels(column_index, &id).len() + 1;
+
+        self.announcer.announce(Announcement {
+            kind,
+            id,
+            column_index,
+            index,
+            column_len,
+        });
+    }
+
+    /// Announces the position the dragged panel would be dropped at.
+    fn announce_moved(&self, id: K, (column_index, index): (usize, usize)) {
+        let column_len = self.other_panels(column_index, &id).len() + 1;
+
+        self.announcer.announce(Announcement {
+            kind: AnnouncementKind::Moved,
+            id,
+            column_index,
+            index,
+            column_len,
+        });
+    }
+
+    /// Returns the layout of a column.
+    fn layout(&self, column_index: usize) -> Layout {
+        self.column_layouts
+            .get(&column_index)
+            .copied()
+            .unwrap_or(self.layout)
+    }
+
+    /// Returns the number of panels in the first row of a grid column.
+    fn grid_row_len(&self, column_index: usize) -> usize {
+        let Some(column) = self.column(column_index) else {
+            return 1;
+        };
+        let panels = self.panels.read_untracked();
+        let tops: Vec<_> = column
+            .read_untracked()
+            .iter()
+            .filter_map(|id| panels.get(id))
+            .map(|panel| panel.get_bounding_client_rect().top())
+            .collect();
+        let Some(first_top) = tops.first() else {
+            return 1;
+        };
+
+        tops.iter()
+            .take_while(|top| (*top - first_top).abs() < 1.0)
+            .count()
+    }
+
+    /// Returns the panel IDs of a column.
+    fn column(&self, column_index: usize) -> Option<RwSignal<Vec<K>>> {
+        self.panel_order.read_untracked().get(column_index).copied()
+    }
+
+    /// Returns the panel IDs of a column, excluding the dragged panel.
+    fn other_panels(&self, column_index: usize, dragged_id: &K) -> Vec<K> {
+        self.column(column_index)
+            .map(|column| {
+                column
+                    .read_untracked()
+                    .iter()
+                    .filter(|panel_id| *panel_id != dragged_id)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the column and row index of a panel.
+    fn position_of(&self, id: &K) -> Option<(usize, usize)> {
+        position_in(
+            &snapshot_panel_order(&self.panel_order.read_untracked()),
+            id,
+        )
+    }
+
+    /// Returns the column and index the dragged panel would be inserted at, not counting the
+    /// dragged panel itself.
+    fn insertion_point(&self, dragged_id: &K, hover_info: &HoverInfo<K>) -> (usize, usize) {
+        let column = self
+            .column(hover_info.column_index)
+            .map(|column| column.get_untracked())
+            .unwrap_or_default();
+        let index = engine::insertion_index(
+            &column,
+            std::slice::from_ref(dragged_id),
+            hover_info.panel.as_ref(),
+        );
+
+        (hover_info.column_index, index)
+    }
+
+    /// Returns the hover info which inserts the dragged panel at an index in a column, not counting
+    /// the dragged panel itself.
+    fn hover_info_at(&self, dragged_id: &K, column_index: usize, index: usize) -> HoverInfo<K> {
+        let other_panels = self.other_panels(column_index, dragged_id);
+        let (before, after) = self.layout(column_index).hover_positions();
+        let panel = match other_panels.get(index) {
+            Some(id) => Some(HoveredPanel {
+                id: id.clone(),
+                position: before,
+            }),
+            None => other_panels.last().map(|id| HoveredPanel {
+                id: id.clone(),
+                position: after,
+            }),
+        };
+
+        HoverInfo {
+            column_index,
+            panel,
+        }
+    }
+}
+
+/// Provides drag reordering for the given columns of panel IDs, returning a node ref for each column
+/// and a [`DragReorderHandle`] to the provider.
+pub fn provide_drag_reorder<const COLUMNS: usize, E, K>(
+    panel_order: [RwSignal<Vec<K>>; COLUMNS],
+) -> ([NodeRef<E>; COLUMNS], DragReorderHandle<K>)
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
+{
+    provide_drag_reorder_with_options(panel_order, DragReorderOptions::default())
+}
+
+/// Version of [`provide_drag_reorder`] that takes [`DragReorderOptions`].
+pub fn provide_drag_reorder_with_options<const COLUMNS: usize, E, K>(
+    panel_order: [RwSignal<Vec<K>>; COLUMNS],
+    options: DragReorderOptions<K>,
+) -> ([NodeRef<E>; COLUMNS], DragReorderHandle<K>)
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
+{
+    let ctx = provide_drag_reorder_context(Signal::stored(panel_order.to_vec()), options);
+
+    let column_refs = panel_order.map(|column| {
+        let column_ref = NodeRef::new();
+        register_column_ref(ctx.column_refs, column, column_ref);
+        column_ref
+    });
+
+    (column_refs, DragReorderHandle::new(ctx))
+}
+
+/// Provides drag reordering for a reactive list of columns, which can be added and removed at runtime.
+///
+/// Each column should call [`use_drag_column_ref`] to get the node ref for its element.
+pub fn provide_drag_reorder_dynamic<K: PanelId>(
+    panel_order: impl Into<Signal<Vec<RwSignal<Vec<K>>>>>,
+) -> DragReorderHandle<K> {
+    provide_drag_reorder_dynamic_with_options(panel_order, DragReorderOptions::default())
+}
+
+/// Version of [`provide_drag_reorder_dynamic`] that takes [`DragReorderOptions`].
+pub fn provide_drag_reorder_dynamic_with_options<K: PanelId>(
+    panel_order: impl Into<Signal<Vec<RwSignal<Vec<K>>>>>,
+    options: DragReorderOptions<K>,
+) -> DragReorderHandle<K> {
+    DragReorderHandle::new(provide_drag_reorder_context(panel_order.into(), options))
+}
+
+/// Handle to a drag reorder provider, used to move panels without dragging, undo and redo moves, and manage
+/// the selection.
+///
+/// It's returned when the provider is created, and from [`use_drag_reorder_handle`] in components below it.
+pub struct DragReorderHandle<K: PanelId> {
+    ctx: StoredValue<DragReorderContext<K>>,
+}
+
+impl<K: PanelId> Clone for DragReorderHandle<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: PanelId> Copy for DragReorderHandle<K> {}
+
+impl<K: PanelId> DragReorderHandle<K> {
+    fn new(ctx: DragReorderContext<K>) -> Self {
+        DragReorderHandle {
+            ctx: StoredValue::new(ctx),
+        }
+    }
+
+    /// Reverts the most recent move or removal.
+    ///
+    /// Does nothing while a panel is being dragged.
+    pub fn undo(&self) {
+        self.ctx.with_value(|ctx| ctx.undo());
+    }
+
+    /// Reapplies the most recently undone move or removal.
+    ///
+    /// Does nothing while a panel is being dragged.
+    pub fn redo(&self) {
+        self.ctx.with_value(|ctx| ctx.redo());
+    }
+
+    /// Returns a signal of the selected panels, in the order they were selected.
+    pub fn selection(&self) -> Signal<Vec<K>> {
+        self.ctx.with_value(|ctx| ctx.selection).into()
+    }
+
+    /// Deselects every panel.
+    pub fn clear_selection(&self) {
+        self.ctx.with_value(|ctx| {
+            ctx.selection.set(Vec::new());
+            ctx.selection_anchor.set(None);
+        });
+    }
+
+    /// Returns a signal which is `true` when there's a move to undo.
+    pub fn can_undo(&self) -> Signal<bool> {
+        let history = self.ctx.with_value(|ctx| ctx.history);
+        Signal::derive(move || history.read().can_undo())
+    }
+
+    /// Returns a signal which is `true` when there's an undone move to redo.
+    pub fn can_redo(&self) -> Signal<bool> {
+        let history = self.ctx.with_value(|ctx| ctx.history);
+        Signal::derive(move || history.read().can_redo())
+    }
+
+    /// Returns the `(column_index, index)` of a panel, or `None` if the provider doesn't have it.
+    pub fn position_of(&self, id: &K) -> Option<(usize, usize)> {
+        let panel_order = self.ctx.with_value(|ctx| ctx.panel_order);
+        position_in(&tracked_panel_order(&panel_order.read()), id)
+    }
+
+    /// Moves a panel to an index in a column, as if it had been dragged there.
+    ///
+    /// The index is the panel's position once moved, and is clamped to the end of the column.
+    /// The move is checked with [`DragReorderOptions::can_drop`], recorded in the history, reported to
+    /// [`DragReorderOptions::on_reorder`] and animated. Does nothing while a panel is being dragged.
+    ///
+    /// Returns `true` if the panel was moved.
+    pub fn move_to(&self, id: &K, column_index: usize, index: usize) -> bool {
+        self.ctx
+            .with_value(|ctx| ctx.move_panel(id, column_index, index))
+    }
+
+    /// Moves a panel one position towards the start of its column, like [`move_to`](Self::move_to).
+    pub fn move_up(&self, id: &K) -> bool {
+        match self.ctx.with_value(|ctx| ctx.position_of(id)) {
+            Some((column_index, index)) if index > 0 => self.move_to(id, column_index, index - 1),
+            _ => false,
+        }
+    }
+
+    /// Moves a panel one position towards the end of its column, like [`move_to`](Self::move_to).
+    pub fn move_down(&self, id: &K) -> bool {
+        match self.ctx.with_value(|ctx| ctx.position_of(id)) {
+            Some((column_index, index)) => self.move_to(id, column_index, index + 1),
+            None => false,
+        }
+    }
+
+    /// Moves a panel to the end of a column, like [`move_to`](Self::move_to).
+    pub fn move_to_column(&self, id: &K, column_index: usize) -> bool {
+        self.move_to(id, column_index, usize::MAX)
+    }
+
+    /// Removes a panel from its column, deselecting it. Does nothing while a panel is being dragged.
+    ///
+    /// The removal is recorded in the history, so [`undo`](Self::undo) puts the panel back, and is reported
+    /// to [`DragReorderOptions::on_remove`]. The panel is rendered again once it's restored, so keep its
+    /// data around while the removal can be undone.
+    ///
+    /// Returns `true` if the panel was removed.
+    pub fn remove(&self, id: &K) -> bool {
+        self.ctx.with_value(|ctx| ctx.remove_panel(id))
+    }
+
+    /// Returns a signal of the provider's [`DragState`].
+    pub fn drag_state(&self) -> Signal<DragState<K>> {
+        self.ctx.with_value(|ctx| ctx.drag_state())
+    }
+
+    /// Returns a signal of the panels of a column to render with [`DragReorderOptions::live_reorder`].
+    ///
+    /// While dragging, this is the provisional order with the dragged panels moved to the hovered position.
+    /// Otherwise, it's the column's panel order.
+    pub fn preview_order(&self, column_index: usize) -> Signal<Vec<K>> {
+        let (panel_order, preview_order) = self
+            .ctx
+            .with_value(|ctx| (ctx.panel_order, ctx.preview_order));
+        Signal::derive(move || match &*preview_order.read() {
+            Some(preview_order) => preview_order.get(column_index).cloned().unwrap_or_default(),
+            None => panel_order
+                .read()
+                .get(column_index)
+                .map(|column| column.get())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Returns a [`DragReorderHandle`] to the nearest provider, the same as the one returned when it was
+/// provided.
+pub fn use_drag_reorder_handle<K: PanelId>() -> DragReorderHandle<K> {
+    DragReorderHandle::new(expect_context())
+}
+
+/// Returns a signal of the nearest provider's [`DragState`], for reacting to drags outside of the panels.
+///
+/// Use [`DragReorderHandle::drag_state`] to read the state of another provider.
+pub fn use_drag_state<K: PanelId>() -> Signal<DragState<K>> {
+    expect_context::<DragReorderContext<K>>().drag_state()
+}
+
+/// Return type of [`use_drag_column`].
+#[derive(Clone, Copy)]
+pub struct UseDragColumnReturn {
+    /// `true` while a dragged panel would be dropped into the column.
+    pub is_drag_over: Signal<bool>,
+    /// The index the dragged panel would be dropped at in the column, not counting the dragged panels.
+    ///
+    /// Useful for rendering a placeholder where the panel will land.
+    pub insertion_index: Signal<Option<usize>>,
+    /// `true` while a dragged panel would be dropped into the column, and the column has no other panels.
+    pub is_empty_target: Signal<bool>,
+}
+
+/// Options for [`use_drag_column_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct UseDragColumnOptions {
+    scope: Option<Oco<'static, str>>,
+}
+
+impl UseDragColumnOptions {
+    /// Uses the provider of a [scope](DragReorderOptions::scope), instead of the nearest provider.
+    pub fn scope(mut self, scope: impl Into<Oco<'static, str>>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+}
+
+/// Returns the drag state of a column of the nearest provider, such as where the dragged panel would
+/// be dropped.
+pub fn use_drag_column<K: PanelId>(column_index: usize) -> UseDragColumnReturn {
+    use_drag_column_with_options::<K>(column_index, UseDragColumnOptions::default())
+}
+
+/// Returns the drag state of a column, with options.
+pub fn use_drag_column_with_options<K: PanelId>(
+    column_index: usize,
+    options: UseDragColumnOptions,
+) -> UseDragColumnReturn {
+    let UseDragColumnOptions { scope } = options;
+    let DragReorderContext {
+        panel_order,
+        currently_dragged_panel,
+        hover_info,
+        incoming_panel,
+        selection,
+        ..
+    } = use_drag_reorder_context::<K>(scope.as_deref());
+
+    let drag_over = Memo::new(move |_| {
+        let hover_info = hover_info.read();
+        let hover_info = hover_info
+            .as_ref()
+            .filter(|hover_info| hover_info.column_index == column_index)?;
+        let dragged_id = currently_dragged_panel
+            .get()
+            .or_else(|| incoming_panel.get())?;
+        Some(tracked_insertion_index(
+            panel_order,
+            selection,
+            &dragged_id,
+            hover_info,
+        ))
+    });
+
+    UseDragColumnReturn {
+        is_drag_over: Signal::derive(move || drag_over.read().is_some()),
+        insertion_index: Signal::derive(move || drag_over.get().map(|(index, _)| index)),
+        is_empty_target: Signal::derive(move || {
+            drag_over.get().is_some_and(|(_, is_empty)| is_empty)
+        }),
+    }
+}
+
+/// Returns the index the dragged panels would be dropped at among the hovered column's other panels, and
+/// whether the column has no other panels, tracking the panel order.
+fn tracked_insertion_index<K: PanelId>(
+    panel_order: Signal<Vec<RwSignal<Vec<K>>>>,
+    selection: RwSignal<Vec<K>>,
+    dragged_id: &K,
+    hover_info: &HoverInfo<K>,
+) -> (usize, bool) {
+    let columns = panel_order
+        .read()
+        .iter()
+        .map(|column| column.get())
+        .collect::<Vec<_>>();
+    let moved = engine::moved_panels(&columns, dragged_id, &selection.read());
+    let column = columns
+        .get(hover_info.column_index)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let index = engine::insertion_index(column, &moved, hover_info.panel.as_ref());
+    let is_empty = column.iter().all(|panel_id| moved.contains(panel_id));
+    (index, is_empty)
+}
+
+/// Registers a column of a [`provide_drag_reorder_dynamic`] provider, returning the node ref which should
+/// be assigned to the column element.
+///
+/// The column is registered with the nearest provider, or the [scoped](DragReorderOptions::scope) provider
+/// which has the column. It's unregistered when the calling component is unmounted.
+pub fn use_drag_column_ref<E, K>(column: RwSignal<Vec<K>>) -> NodeRef<E>
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
+{
+    let has_column =
+        |ctx: &DragReorderContext<K>| ctx.panel_order.read_untracked().contains(&column);
+    let ctx = match use_context::<DragReorderContext<K>>() {
+        Some(ctx) if has_column(&ctx) => ctx,
+        nearest => use_context::<DragReorderScopes<K>>()
+            .and_then(|scopes| scopes.0.into_values().find(has_column))
+            .or(nearest)
+            .unwrap_or_else(expect_context),
+    };
+    let DragReorderContext { column_refs, .. } = ctx;
+    let column_ref = NodeRef::new();
+    register_column_ref(column_refs, column, column_ref);
+
+    on_cleanup(move || {
+        column_refs.write().remove(&column);
+    });
+
+    column_ref
+}
+
+/// Keeps the element of a column in the context's column refs.
+fn register_column_ref<E, K>(
+    column_refs: ColumnRefs<K>,
+    column: RwSignal<Vec<K>>,
+    column_ref: NodeRef<E>,
+) where
+    E: ElementType + 'static,
+    E::Output: JsCast + Into<web_sys::Element> + Clone + 'static,
+    K: PanelId,
+{
+    Effect::new(move |_| match column_ref.get() {
+        Some(column_ref) => {
+            column_refs
+                .write()
+                .insert(column, SendWrapper::new(column_ref.into()));
+        }
+        None => {
+            column_refs.write().remove(&column);
+        }
+    });
+}
+
+fn provide_drag_reorder_context<K: PanelId>(
+    panel_order: Signal<Vec<RwSignal<Vec<K>>>>,
+    options: DragReorderOptions<K>,
+) -> DragReorderContext<K> {
+    let DragReorderOptions {
+        scope,
+        group,
+        backend,
+        layout,
+        column_layouts,
+        announcement_formatter,
+        on_reorder,
+        on_cancel,
+        on_remove,
+        can_drop,
+        history_depth,
+        animation,
+        live_reorder,
+        auto_scroll,
+        #[cfg(feature = "persist")]
+        persist,
+    } = options;
+
+    #[cfg(feature = "persist")]
+    if let Some(persist) = persist {
+        if cfg!(any(feature = "ssr", feature = "hydrate")) {
+            // The server has no storage, and hydration must render the same layout as the server,
+            // so the stored layout is restored once running on the client
+            Effect::new(move |_| {
+                persist.restore(&panel_order.read_untracked());
+                persist.clone().save_changes(panel_order);
+            });
+        } else {
+            persist.restore(&panel_order.read_untracked());
+            persist.save_changes(panel_order);
+        }
+    }
+
+    let ctx = DragReorderContext {
+        panel_order,
+        column_refs: RwSignal::new(HashMap::new()),
+        currently_dragged_panel: RwSignal::new(None),
+        hover_info: RwSignal::new(None),
+        incoming_panel: RwSignal::new(None),
+        panels: RwSignal::new(HashMap::new()),
+        keyboard_dragging: RwSignal::new(false),
+        drag_offset: StoredValue::new((0.0, 0.0)),
+        group,
+        scope: scope.clone(),
+        backend,
+        layout,
+        column_layouts,
+        announcer: Announcer::new(announcement_formatter),
+        on_reorder,
+        on_cancel,
+        on_remove,
+        can_drop,
+        history: RwSignal::new(History::new(history_depth)),
+        known_panel_order: StoredValue::new(snapshot_panel_order(&panel_order.read_untracked())),
+        animation,
+        live_reorder,
+        preview_order: RwSignal::new(None),
+        auto_scroller: AutoScroller::new(auto_scroll),
+        selection: RwSignal::new(Vec::new()),
+        selection_anchor: RwSignal::new(None),
+    };
+
+    Effect::new({
+        let ctx = ctx.clone();
+        move |last: Option<LastAnnouncement<K>>| {
+            let (last_dragged_panel, last_insertion_point) = last.unwrap_or_default();
+            let currently_dragged_panel = ctx.currently_dragged_panel.get();
+            let incoming_panel = ctx.incoming_panel.get();
+            let hover_info = ctx.hover_info.get();
+
+            let Some(id) = currently_dragged_panel.clone().or(incoming_panel) else {
+                return (None, None);
+            };
+            if currently_dragged_panel.is_some() && last_dragged_panel.as_ref() != Some(&id) {
+                ctx.announce_position(AnnouncementKind::PickedUp, id);
+                return (currently_dragged_panel, None);
+            }
+
+            // Only moves to another position are announced, not every change of the hovered panel
+            let insertion_point =
+                hover_info.map(|hover_info| ctx.insertion_point(&id, &hover_info));
+            if let Some(insertion_point) = insertion_point {
+                if last_insertion_point != Some(insertion_point) {
+                    ctx.announce_moved(id, insertion_point);
+                }
+            }
+
+            (currently_dragged_panel, insertion_point)
+        }
+    });
+
+    // Moves are recorded with absolute positions, which no longer apply once the panel order is changed
+    // outside of the provider, such as by adding a panel
+    Effect::new({
+        let ctx = ctx.clone();
+        move |_| {
+            let panel_order = tracked_panel_order(&ctx.panel_order.read());
+            if ctx
+                .known_panel_order
+                .with_value(|known| *known != panel_order)
+            {
+                ctx.known_panel_order.set_value(panel_order);
+                ctx.history.maybe_update(|history| {
+                    let is_empty = !history.can_undo() && !history.can_redo();
+                    history.clear();
+                    !is_empty
+                });
+            }
+        }
+    });
+
+    // Panels which are no longer in any column are deselected
+    Effect::new({
+        let ctx = ctx.clone();
+        move |_| {
+            let panel_ids: HashSet<K> = tracked_panel_order(&ctx.panel_order.read())
+                .into_iter()
+                .flatten()
+                .collect();
+            ctx.selection.maybe_update(|selection| {
+                let len = selection.len();
+                selection.retain(|selected| panel_ids.contains(selected));
+                selection.len() != len
+            });
+            ctx.selection_anchor.maybe_update(|anchor| {
+                if anchor
+                    .as_ref()
+                    .is_some_and(|anchor| !panel_ids.contains(anchor))
+                {
+                    *anchor = None;
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+    });
+
+    if live_reorder {
+        Effect::new({
+            let ctx = ctx.clone();
+            move |_| {
+                let preview_order = ctx
+                    .currently_dragged_panel
+                    .get()
+                    .zip(ctx.hover_info.get())
+                    .map(|(dragged_id, hover_info)| {
+                        engine::reorder(
+                            &snapshot_panel_order(&ctx.panel_order.read_untracked()),
+                            &dragged_id,
+                            &ctx.selection.read_untracked(),
+                            &hover_info,
+                        )
+                    });
+                if preview_order != ctx.preview_order.get_untracked() {
+                    animate_reorder(ctx.animation.as_ref(), &[ctx.panels], || {
+                        ctx.preview_order.set(preview_order);
+                    });
+                }
+            }
+        });
+    }
+
+    if backend == DragBackend::Html5 {
+        Effect::new({
+            let ctx = ctx.clone();
+            move |_| {
+                let on_dragover: Function = Closure::wrap(Box::new({
+                    let ctx = ctx.clone();
+                    move |ev: web_sys::DragEvent| {
+                        if !ctx.is_native_dragging() {
+                            return;
+                        }
+
+                        let (mouse_x, mouse_y) = (ev.client_x() as f64, ev.client_y() as f64);
+                        ctx.auto_scroller.update(ctx.column_refs, mouse_x, mouse_y);
+                        let can_drop =
+                            ctx.update_hover_info(mouse_x, mouse_y, ctx.drag_offset.get_value());
+                        // Only accepting the drop where it's allowed makes the browser cancel it elsewhere
+                        if can_drop {
+                            ev.prevent_default();
+                        } else if let Some(data_transfer) = ev.data_transfer() {
+                            data_transfer.set_drop_effect("none");
+                        }
+                    }
+                }) as Box<dyn FnMut(_)>)
+                .into_js_value()
+                .dyn_into()
+                .unwrap();
+
+                // Drags which end without a drop, such as by pressing Escape or dropping outside of every
+                // column, are cancelled by the dragged panel's `dragend` listener
+                let on_drop: Function = Closure::wrap(Box::new({
+                    let ctx = ctx.clone();
+                    move |ev: web_sys::DragEvent| {
+                        if !ctx.is_native_dragging() {
+                            return;
+                        }
+                        // Stop the browser from opening the drag data
+                        ev.prevent_default();
+                        ctx.drop_dragged_panel();
+                        ctx.end_drag();
+                    }
+                }) as Box<dyn FnMut(_)>)
+                .into_js_value()
+                .dyn_into()
+                .unwrap();
+
+                let listeners = vec![("dragover", on_dragover), ("drop", on_drop)];
+                for (event, listener) in &listeners {
+                    document()
+                        .add_event_listener_with_callback(event, listener)
+                        .unwrap();
+                }
+
+                on_cleanup({
+                    let listeners = SendWrapper::new(listeners);
+                    move || {
+                        for (event, listener) in listeners.take() {
+                            let _ =
+                                document().remove_event_listener_with_callback(event, &listener);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    // Panels are never dragged on the server, and requests can share a thread
+    if ctx.group.is_some() && !cfg!(feature = "ssr") {
+        DRAG_GROUPS.with_borrow_mut(|providers| providers.push(Box::new(ctx.clone())));
+        on_cleanup({
+            let ctx = ctx.clone();
+            move || {
+                DRAG_GROUPS.with_borrow_mut(|providers| {
+                    providers.retain(|provider| {
+                        provider
+                            .downcast_ref::<DragReorderContext<K>>()
+                            .is_none_or(|provider| !provider.is_same_provider(&ctx))
+                    })
+                });
+            }
+        });
+    }
+
+    if let Some(scope) = scope {
+        let mut scopes = use_context::<DragReorderScopes<K>>()
+            .unwrap_or_else(|| DragReorderScopes(HashMap::new()));
+        scopes.0.insert(scope, ctx.clone());
+        provide_context(scopes);
+    }
+    provide_context(ctx.clone());
+
+    ctx
+}
+
+thread_local! {
+    /// Providers which are part of a [`DragGroup`], type erased over their panel ID type.
+    static DRAG_GROUPS: RefCell<Vec<Box<dyn Any>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the providers in a [`DragGroup`] which use the panel ID type `K`.
+fn drag_group_providers<K: PanelId>() -> Vec<DragReorderContext<K>> {
+    DRAG_GROUPS.with_borrow(|providers| {
+        providers
+            .iter()
+            .filter_map(|provider| provider.downcast_ref::<DragReorderContext<K>>().cloned())
+            .collect()
+    })
+}
+
+/// Drag reorder contexts provided with a scope, keyed by their scope.
+#[derive(Clone)]
+struct DragReorderScopes<K: PanelId>(HashMap<Oco<'static, str>, DragReorderContext<K>>);
+
+/// Returns the context for a scope, or the nearest context if no scope is given.
+fn use_drag_reorder_context<K: PanelId>(scope: Option<&str>) -> DragReorderContext<K> {
+    match scope {
+        Some(scope) => use_context::<DragReorderScopes<K>>()
+            .and_then(|scopes| scopes.0.get(scope).cloned())
+            .unwrap_or_else(|| panic!("drag reorder scope `{scope}` has not been provided")),
+        None => expect_context(),
+    }
+}
+
+/// Moves the dragged panel to the hovered position, along with the rest of the selection if it's selected.
+fn reorder_panel_order<K: PanelId>(
+    panel_order: &[RwSignal<Vec<K>>],
+    currently_dragged_panel: &K,
+    selection: &[K],
+    hover_info: HoverInfo<K>,
+) -> Option<ReorderEvent<K>> {
+    let before = snapshot_panel_order(panel_order);
+    let placements =
+        engine::drop_placements(&before, currently_dragged_panel, selection, &hover_info);
+    place_panels(panel_order, currently_dragged_panel, before, &placements)
+}
+
+/// Moves panels to new `(column_index, index)` positions in a single update, returning the move of the
+/// panel `id` unless nothing changed.
+///
+/// The panels are removed from the columns, then inserted in order of their new position. Panels which
+/// are missing, or placed in a column which doesn't exist, are left alone.
+fn place_panels<K: PanelId>(
+    panel_order: &[RwSignal<Vec<K>>],
+    id: &K,
+    before: Vec<Vec<K>>,
+    placements: &[(K, (usize, usize))],
+) -> Option<ReorderEvent<K>> {
+    let ids = engine::valid_placements(&before, placements)
+        .into_iter()
+        .map(|(panel_id, _)| panel_id.clone())
+        .collect::<Vec<_>>();
+    let after = engine::place(&before, placements);
+
+    // Panels dropped back where they started aren't reported
+    if after == before {
+        return None;
+    }
+
+    for ((column, column_before), column_after) in panel_order.iter().zip(&before).zip(&after) {
+        if column_before != column_after {
+            column.set(column_after.clone());
+        }
+    }
+
+    let (from_column, from_index) = position_in(&before, id)?;
+    let (to_column, to_index) = position_in(&after, id)?;
+    Some(ReorderEvent {
+        id: id.clone(),
+        ids,
+        from_column,
+        from_index,
+        to_column,
+        to_index,
+        before,
+        after,
+        transfer: None,
+    })
+}
+
+/// The dragged panel and insertion point of the last announcement.
+type LastAnnouncement<K> = (Option<K>, Option<(usize, usize)>);
+
+fn snapshot_panel_order<K: PanelId>(panel_order: &[RwSignal<Vec<K>>]) -> Vec<Vec<K>> {
+    panel_order
+        .iter()
+        .map(|column| column.get_untracked())
+        .collect()
+}
+
+fn tracked_panel_order<K: PanelId>(panel_order: &[RwSignal<Vec<K>>]) -> Vec<Vec<K>> {
+    panel_order.iter().map(|column| column.get()).collect()
+}
@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use leptos::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::PanelId;
+
+/// The version of the stored layout format. Layouts stored with a different version are ignored.
+const SCHEMA_VERSION: u32 = 1;
+
+/// The browser storage a layout is persisted to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PersistStorage {
+    /// `localStorage`, which is kept across browser sessions.
+    #[default]
+    Local,
+    /// `sessionStorage`, which is cleared when the tab is closed.
+    Session,
+}
+
+/// Options for persisting a provider's panel order, passed to
+/// [`DragReorderOptions::persist`](crate::DragReorderOptions::persist).
+///
+/// The stored layout is restored when the provider is created, and saved each time the panel order changes.
+/// Panels which no longer exist are dropped from the stored layout, and panels which aren't in the stored
+/// layout are appended to the [default column](Persist::default_column).
+#[derive(Clone, Debug)]
+pub struct Persist {
+    key: Oco<'static, str>,
+    storage: PersistStorage,
+    default_column: usize,
+}
+
+impl Persist {
+    /// Persists the layout under a storage key.
+    pub fn new(key: impl Into<Oco<'static, str>>) -> Self {
+        Persist {
+            key: key.into(),
+            storage: PersistStorage::default(),
+            default_column: 0,
+        }
+    }
+
+    /// Sets the storage the layout is persisted to. Defaults to [`PersistStorage::Local`].
+    pub fn storage(mut self, storage: PersistStorage) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Sets the column which new panels are appended to when restoring a layout. Defaults to the first
+    /// column.
+    pub fn default_column(mut self, column_index: usize) -> Self {
+        self.default_column = column_index;
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct StoredLayoutRef<'a, K> {
+    version: u32,
+    columns: &'a [Vec<K>],
+}
+
+#[derive(Deserialize)]
+struct StoredVersion {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct StoredLayout<K> {
+    columns: Vec<Vec<K>>,
+}
+
+/// [`Persist`] options along with the serialization functions of the panel ID type.
+#[derive(Clone, Debug)]
+pub(crate) struct Persistence<K> {
+    options: Persist,
+    serialize: fn(&[Vec<K>]) -> Option<String>,
+    deserialize: fn(&str) -> Option<Vec<Vec<K>>>,
+}
+
+impl<K: PanelId> Persistence<K> {
+    pub(crate) fn new(options: Persist) -> Self
+    where
+        K: Serialize + DeserializeOwned,
+    {
+        Persistence {
+            options,
+            serialize: |columns| {
+                serde_json::to_string(&StoredLayoutRef {
+                    version: SCHEMA_VERSION,
+                    columns,
+                })
+                .ok()
+            },
+            deserialize: |value| {
+                let StoredVersion { version } = serde_json::from_str(value).ok()?;
+                if version != SCHEMA_VERSION {
+                    return None;
+                }
+                let StoredLayout { columns } = serde_json::from_str(value).ok()?;
+                Some(columns)
+            },
+        }
+    }
+
+    /// Replaces the panel order with the stored layout, if there is one.
+    pub(crate) fn restore(&self, panel_order: &[RwSignal<Vec<K>>]) {
+        let Some(stored) = self
+            .storage()
+            .and_then(|storage| storage.get_item(&self.options.key).ok().flatten())
+            .and_then(|value| (self.deserialize)(&value))
+        else {
+            return;
+        };
+
+        let current = panel_order
+            .iter()
+            .map(|column| column.get_untracked())
+            .collect::<Vec<_>>();
+        let restored = reconcile(stored, &current, self.options.default_column);
+        for ((column, current), restored) in panel_order.iter().zip(current).zip(restored) {
+            if current != restored {
+                column.set(restored);
+            }
+        }
+    }
+
+    /// Saves the panel order each time it changes.
+    pub(crate) fn save_changes(self, panel_order: Signal<Vec<RwSignal<Vec<K>>>>) {
+        Effect::new(move |_| {
+            let columns = panel_order
+                .read()
+                .iter()
+                .map(|column| column.get())
+                .collect::<Vec<_>>();
+            let Some(value) = (self.serialize)(&columns) else {
+                return;
+            };
+            if let Some(storage) = self.storage() {
+                let _ = storage.set_item(&self.options.key, &value);
+            }
+        });
+    }
+
+    fn storage(&self) -> Option<web_sys::Storage> {
+        let storage = match self.options.storage {
+            PersistStorage::Local => window().local_storage(),
+            PersistStorage::Session => window().session_storage(),
+        };
+        storage.ok().flatten()
+    }
+}
+
+/// Arranges the current panels in the stored layout.
+///
+/// Stored panels which no longer exist are dropped, and current panels missing from the stored layout are
+/// appended to the default column.
+fn reconcile<K: PanelId>(
+    stored: Vec<Vec<K>>,
+    current: &[Vec<K>],
+    default_column: usize,
+) -> Vec<Vec<K>> {
+    let mut unplaced = current.iter().flatten().collect::<HashSet<_>>();
+    let mut restored = stored
+        .into_iter()
+        .chain(std::iter::repeat_with(Vec::new))
+        .take(current.len())
+        .map(|column| {
+            column
+                .into_iter()
+                .filter(|id| unplaced.remove(id))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let default_column = default_column.min(restored.len().saturating_sub(1));
+    if let Some(column) = restored.get_mut(default_column) {
+        column.extend(
+            current
+                .iter()
+                .flatten()
+                .filter(|id| unplaced.contains(id))
+                .cloned(),
+        );
+    }
+
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn reconcile_restores_stored_layout() {
+        let stored = vec![vec![3, 1], vec![2]];
+        let current = vec![vec![1, 2], vec![3]];
+        assert_eq!(reconcile(stored.clone(), &current, 0), stored);
+    }
+
+    #[test]
+    fn reconcile_drops_removed_panels() {
+        let stored = vec![vec![1, 4], vec![2, 5]];
+        let current = vec![vec![1], vec![2]];
+        assert_eq!(reconcile(stored, &current, 0), vec![vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn reconcile_appends_new_panels_to_default_column() {
+        let stored = vec![vec![2], vec![1]];
+        let current = vec![vec![1, 3], vec![2, 4]];
+        assert_eq!(
+            reconcile(stored.clone(), &current, 1),
+            vec![vec![2], vec![1, 3, 4]]
+        );
+
+        // Out of range default columns fall back to the last column
+        assert_eq!(reconcile(stored, &current, 5), vec![vec![2], vec![1, 3, 4]]);
+    }
+
+    #[test]
+    fn reconcile_matches_current_column_count() {
+        let stored = vec![vec![1], vec![2], vec![3]];
+        let current = vec![vec![1, 2, 3], vec![]];
+        assert_eq!(reconcile(stored, &current, 0), vec![vec![1, 3], vec![2]]);
+
+        let stored = vec![vec![2, 1]];
+        let current = vec![vec![1], vec![2], vec![3]];
+        assert_eq!(
+            reconcile(stored, &current, 0),
+            vec![vec![2, 1, 3], vec![], vec![]]
+        );
+    }
+
+    #[test]
+    fn reconcile_skips_duplicate_stored_panels() {
+        let stored = vec![vec![1, 2], vec![1]];
+        let current = vec![vec![1, 2], vec![]];
+        assert_eq!(reconcile(stored, &current, 0), vec![vec![1, 2], vec![]]);
+    }
+
+    #[test]
+    fn reconcile_without_columns_is_empty() {
+        let stored = vec![vec![1]];
+        assert_eq!(reconcile(stored, &[], 0), Vec::<Vec<u32>>::new());
+    }
+
+    /// Columns of unique panel IDs.
+    fn columns() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        prop::collection::vec(prop::collection::vec(0..20u32, 0..5), 1..4).prop_map(|columns| {
+            let mut seen = HashSet::new();
+            columns
+                .into_iter()
+                .map(|column| column.into_iter().filter(|id| seen.insert(*id)).collect())
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn reconcile_keeps_every_current_panel_once(
+            stored in columns(),
+            current in columns(),
+            default_column in 0..4usize,
+        ) {
+            let restored = reconcile(stored, &current, default_column);
+            prop_assert_eq!(restored.len(), current.len());
+
+            let mut restored_ids = restored.into_iter().flatten().collect::<Vec<_>>();
+            let mut current_ids = current.into_iter().flatten().collect::<Vec<_>>();
+            restored_ids.sort_unstable();
+            current_ids.sort_unstable();
+            prop_assert_eq!(restored_ids, current_ids);
+        }
+    }
+}